@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use super::camera_bookmarks_resource::CameraBookmarks;
+use super::pan_cam::PanCam;
+
+const SLOT_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+// 1-9 fly to a bookmark, Shift+1-9 keeps the current view in it
+pub fn update_bookmark_keys(
+    keyboard: Res<Input<KeyCode>>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut cam_query: Query<(&mut PanCam, &Transform, &OrthographicProjection)>,
+) {
+    let Some(slot) = SLOT_KEYS.iter().position(|key| keyboard.just_pressed(*key)) else {
+        return;
+    };
+    if keyboard.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        return;
+    }
+    let is_shift = keyboard.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+
+    for (mut cam, transform, projection) in &mut cam_query {
+        if !cam.enabled {
+            continue;
+        }
+        if is_shift {
+            let center = transform.translation.truncate();
+            if !bookmarks.store(Some(slot), center, projection.scale) {
+                warn!(
+                    "Bookmark {} is not kept, the next free one is {}",
+                    slot + 1,
+                    bookmarks.bookmarks.len() + 1
+                );
+            }
+        } else if let Some(bookmark) = bookmarks.bookmarks.get(slot) {
+            cam.fly_to(bookmark.center(), bookmark.scale);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use galaxy::bookmark::{self, CameraBookmark};
+
+/// Camera views of the current preset, saved and loaded with it
+#[derive(Resource, Default)]
+pub struct CameraBookmarks {
+    pub bookmarks: Vec<CameraBookmark>,
+}
+
+impl CameraBookmarks {
+    /// Keep the view in the slot, keeping its name, or as a new bookmark at the end
+    /// without a slot. Slots past the end are refused, the view would not be in them.
+    pub fn store(&mut self, slot: Option<usize>, center: Vec2, scale: f32) -> bool {
+        let slot = slot.unwrap_or(self.bookmarks.len());
+        if let Some(bookmark) = self.bookmarks.get_mut(slot) {
+            *bookmark = CameraBookmark::new(&bookmark.name, center, scale);
+        } else if slot == self.bookmarks.len() {
+            let name = bookmark::next_free_name(&self.bookmarks);
+            self.bookmarks
+                .push(CameraBookmark::new(&name, center, scale));
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Surrounding spaces are dropped, an empty name keeps the old one
+    pub fn rename(&mut self, index: usize, name: &str) {
+        let name = name.trim();
+        if let Some(bookmark) = self.bookmarks.get_mut(index).filter(|_| !name.is_empty()) {
+            bookmark.name = name.to_string();
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.bookmarks.len() {
+            self.bookmarks.remove(index);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use galaxy::camera_path::{self, CameraPath, Keyframe};
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraPathState {
+    Idle,
+    /// Keyframe times are counted from `started`, in real seconds
+    Recording {
+        started: f64,
+    },
+    Playing {
+        elapsed: f32,
+    },
+}
+
+/// Camera path being recorded or played, and the saved ones
+#[derive(Resource)]
+pub struct CameraPathRecorder {
+    pub dir: PathBuf,
+    pub names: Vec<String>,
+    pub status: String,
+    pub path: CameraPath,
+    pub state: CameraPathState,
+}
+
+impl Default for CameraPathRecorder {
+    fn default() -> Self {
+        let dir = PathBuf::from(camera_path::CAMERA_PATHS_DIR);
+        let names = camera_path::list_camera_paths(&dir);
+        Self {
+            dir,
+            names,
+            status: String::new(),
+            path: CameraPath::default(),
+            state: CameraPathState::Idle,
+        }
+    }
+}
+
+impl CameraPathRecorder {
+    pub fn refresh(&mut self) {
+        self.names = camera_path::list_camera_paths(&self.dir);
+    }
+
+    /// Add the view as a keyframe, the first one starts a new path.
+    /// Ignored while playing, the played path would be lost.
+    pub fn record(&mut self, now: f64, center: Vec2, scale: f32, years: f64) {
+        let started = match self.state {
+            CameraPathState::Recording { started } => started,
+            CameraPathState::Playing { .. } => {
+                self.status = "Stop playing to record a new path".to_string();
+                return;
+            }
+            CameraPathState::Idle => {
+                self.path = CameraPath::default();
+                self.state = CameraPathState::Recording { started: now };
+                now
+            }
+        };
+        let time = (now - started) as f32;
+        self.path.push(Keyframe::new(time, center, scale, years));
+        self.status = format!(
+            "Recording {} keyframe(s), {:.1} s",
+            self.path.len(),
+            self.path.duration()
+        );
+    }
+
+    /// Play from the start, a path needs two keyframes
+    pub fn play(&mut self) {
+        if self.path.len() < 2 {
+            self.state = CameraPathState::Idle;
+            self.status = "Record at least two keyframes to play".to_string();
+            return;
+        }
+        self.state = CameraPathState::Playing { elapsed: 0. };
+        self.status = format!("Playing {:.1} s", self.path.duration());
+    }
+
+    pub fn stop(&mut self) {
+        if self.state != CameraPathState::Idle {
+            self.state = CameraPathState::Idle;
+            self.status = format!(
+                "{} keyframe(s), {:.1} s",
+                self.path.len(),
+                self.path.duration()
+            );
+        }
+    }
+
+    /// Save under a new name
+    pub fn save(&mut self) {
+        self.stop();
+        if self.path.is_empty() {
+            self.status = "Nothing recorded to save".to_string();
+            return;
+        }
+        let name = camera_path::next_free_name(&self.dir);
+        let path = camera_path::camera_path_file(&self.dir, &name);
+        match self.path.save(&path) {
+            Ok(()) => {
+                info!("Camera path {} is saved", path.display());
+                self.status = format!("Saved {}", name);
+            }
+            Err(err) => {
+                warn!("Unable to save camera path {}: {}", path.display(), err);
+                self.status = format!("Save failed: {}", err);
+            }
+        }
+        self.refresh();
+    }
+
+    pub fn load(&mut self, name: &str) {
+        self.stop();
+        let path = camera_path::camera_path_file(&self.dir, name);
+        match CameraPath::load(&path) {
+            Ok(camera_path) => {
+                info!("Camera path {} is loaded", path.display());
+                self.status = format!(
+                    "Loaded {}, {} keyframe(s), {:.1} s",
+                    name,
+                    camera_path.len(),
+                    camera_path.duration()
+                );
+                self.path = camera_path;
+            }
+            Err(err) => {
+                warn!("Unable to load camera path {}: {}", path.display(), err);
+                self.status = format!("Load failed: {}", err);
+            }
+        }
+    }
+}
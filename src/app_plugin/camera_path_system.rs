@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+use super::camera_path_resource::{CameraPathRecorder, CameraPathState};
+use super::frame_recorder_resource::FrameRecorder;
+use super::pan_cam::PanCam;
+use super::simulation_time_resource::SimulationTime;
+
+// K records the current view as a keyframe, P plays the path or stops
+pub fn update_camera_path_keys(
+    time: Res<Time>,
+    keyboard: Res<Input<KeyCode>>,
+    sim_time: Res<SimulationTime>,
+    mut recorder: ResMut<CameraPathRecorder>,
+    cam_query: Query<(&Transform, &OrthographicProjection), With<PanCam>>,
+) {
+    if keyboard.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::K) {
+        if let Ok((transform, projection)) = cam_query.get_single() {
+            recorder.record(
+                time.elapsed_seconds_f64(),
+                transform.translation.truncate(),
+                projection.scale,
+                sim_time.years,
+            );
+        }
+    }
+    if keyboard.just_pressed(KeyCode::P) {
+        match recorder.state {
+            CameraPathState::Playing { .. } => recorder.stop(),
+            _ => recorder.play(),
+        }
+    }
+}
+
+/// Move the camera and the epoch along the path while playing
+pub fn play_camera_path(
+    time: Res<Time>,
+    frame_recorder: Res<FrameRecorder>,
+    mut recorder: ResMut<CameraPathRecorder>,
+    mut sim_time: ResMut<SimulationTime>,
+    mut cam_query: Query<(&mut PanCam, &mut Transform, &mut OrthographicProjection)>,
+) {
+    let CameraPathState::Playing { elapsed } = recorder.state else {
+        return;
+    };
+    let elapsed = elapsed + frame_recorder.step_seconds(&time) as f32;
+    let Some(state) = recorder.path.sample(elapsed) else {
+        recorder.stop();
+        return;
+    };
+
+    for (mut cam, mut transform, mut projection) in &mut cam_query {
+        // The path has the whole view, nothing else moves the camera meanwhile
+        if cam.follow.is_some() || cam.flight.is_some() {
+            cam.unfollow();
+            cam.flight = None;
+        }
+        cam.zoom_target = None;
+        cam.pan_velocity = Vec2::ZERO;
+        transform.translation = state.center.extend(transform.translation.z);
+        projection.scale = state.scale;
+    }
+    sim_time.seek(state.years);
+
+    if elapsed >= recorder.path.duration() {
+        recorder.stop();
+    } else {
+        recorder.state = CameraPathState::Playing { elapsed };
+    }
+}
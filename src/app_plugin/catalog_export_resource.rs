@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use galaxy::export::{self, ExportFormat};
+use galaxy::generator::GalaxyObject;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use super::galaxy_setting_component::GalaxySettings;
+
+pub const EXPORTS_DIR: &str = "exports";
+
+/// Where catalogs exported from the application are written
+#[derive(Resource)]
+pub struct CatalogExport {
+    pub dir: PathBuf,
+    pub status: String,
+}
+
+impl Default for CatalogExport {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from(EXPORTS_DIR),
+            status: String::new(),
+        }
+    }
+}
+
+impl CatalogExport {
+    /// Write the objects at the epoch to a new file named after the seed and epoch
+    pub fn export(
+        &mut self,
+        format: ExportFormat,
+        objects: &[GalaxyObject],
+        galaxy_settings: &GalaxySettings,
+        years: f64,
+    ) {
+        let path = self.dir.join(format!(
+            "galaxy_{}_{:.0}y.{}",
+            galaxy_settings.seed,
+            years,
+            format.extension()
+        ));
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|_| write_catalog(&path, format, objects, galaxy_settings, years));
+        match result {
+            Ok(()) => {
+                info!("{} objects exported to {}", objects.len(), path.display());
+                self.status = format!("Exported {}", path.display());
+            }
+            Err(err) => {
+                warn!("Unable to export {}: {}", path.display(), err);
+                self.status = format!("Export failed: {}", err);
+            }
+        }
+    }
+}
+
+fn write_catalog(
+    path: &Path,
+    format: ExportFormat,
+    objects: &[GalaxyObject],
+    galaxy_settings: &GalaxySettings,
+    years: f64,
+) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    export::write(&mut writer, format, objects, galaxy_settings, years)?;
+    writer.flush()
+}
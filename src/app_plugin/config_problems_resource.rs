@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use galaxy::validation::ConfigProblem;
+
+/// Problems found while reading settings, shown in the UI until fixed
+#[derive(Resource, Default)]
+pub struct ConfigProblems {
+    /// Source file or preset and the problem found in it
+    pub problems: Vec<(String, ConfigProblem)>,
+}
+
+impl ConfigProblems {
+    /// Log the problems of a source, replacing the ones reported for it before
+    pub fn report(&mut self, source: &str, problems: Vec<ConfigProblem>) {
+        self.problems
+            .retain(|(problem_source, _)| problem_source != source);
+        for problem in problems {
+            warn!("{}: {}", source, problem);
+            self.problems.push((source.to_string(), problem));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.problems.clear();
+    }
+}
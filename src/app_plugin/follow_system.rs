@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use super::pan_cam::PanCam;
+use super::selected_star_resource::SelectedStar;
+
+// F follows the selected object or stops following, C toggles co-rotation
+pub fn update_follow(
+    keyboard: Res<Input<KeyCode>>,
+    selected: Res<SelectedStar>,
+    mut cam_query: Query<&mut PanCam>,
+) {
+    for mut cam in &mut cam_query {
+        if keyboard.just_pressed(KeyCode::F) {
+            match selected.entity {
+                Some(entity) if cam.follow != Some(entity) => cam.follow(entity),
+                _ => cam.unfollow(),
+            }
+        }
+        if keyboard.just_pressed(KeyCode::C) {
+            cam.co_rotate = !cam.co_rotate;
+        }
+        if keyboard.just_pressed(KeyCode::Escape) && cam.follow.is_some() {
+            cam.unfollow();
+        }
+    }
+}
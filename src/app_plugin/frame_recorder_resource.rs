@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use galaxy::frames;
+use galaxy::render::Particle;
+use std::path::PathBuf;
+
+/// Frames written while the application runs. They are drawn on the CPU by the
+/// offline renderer of `galaxy render`, not read back from the window, so they
+/// look like its images and not exactly like the screen.
+/// While recording the simulation steps by one frame of video per frame,
+/// however long drawing it takes.
+#[derive(Resource)]
+pub struct FrameRecorder {
+    pub dir: PathBuf,
+    pub fps: f32,
+    pub recording: Option<FrameRecording>,
+    pub status: String,
+}
+
+pub struct FrameRecording {
+    /// Take directory of the frames
+    pub dir: PathBuf,
+    /// Next frame to write
+    pub frame: u32,
+    /// Stop when the camera path is played to its end
+    pub until_path_end: bool,
+    pub particle: Particle,
+}
+
+impl Default for FrameRecorder {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from(frames::FRAMES_DIR),
+            fps: 30.,
+            recording: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl FrameRecorder {
+    /// Seconds the simulation advances this frame, the real ones when not recording.
+    /// The first recorded frame shows the moment the recording started.
+    pub fn step_seconds(&self, time: &Time) -> f64 {
+        match &self.recording {
+            Some(recording) if recording.frame == 0 => 0.,
+            Some(_) => 1. / self.fps as f64,
+            None => time.delta_seconds_f64(),
+        }
+    }
+
+    pub fn start(&mut self, until_path_end: bool) {
+        let dir = frames::next_free_take(&self.dir);
+        info!("Recording frames to {}", dir.display());
+        self.status = format!("Rendering offline to {}", dir.display());
+        self.recording = Some(FrameRecording {
+            dir,
+            frame: 0,
+            until_path_end,
+            particle: Particle::default(),
+        });
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(recording) = self.recording.take() {
+            info!(
+                "{} frames written to {}",
+                recording.frame,
+                recording.dir.display()
+            );
+            self.status = format!(
+                "{} frames written to {}",
+                recording.frame,
+                recording.dir.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::core_pipeline::bloom::BloomSettings;
+use bevy::prelude::*;
+use galaxy::frames;
+use galaxy::render::{self, Bloom, View};
+use std::fs;
+
+use super::camera_path_resource::{CameraPathRecorder, CameraPathState};
+use super::frame_recorder_resource::FrameRecorder;
+use super::pan_cam::PanCam;
+use super::settings_resource::GalaxySettingsResource;
+use super::simulation_time_resource::SimulationTime;
+use super::star_component::{self, KindComponent, ObjectIndex, StarComponent};
+
+// F9 starts or stops recording frames
+pub fn update_frame_recorder_keys(
+    keyboard: Res<Input<KeyCode>>,
+    mut recorder: ResMut<FrameRecorder>,
+) {
+    if keyboard.just_pressed(KeyCode::F9) {
+        match recorder.recording {
+            Some(_) => recorder.stop(),
+            None => recorder.start(false),
+        }
+    }
+}
+
+/// Draw the view of the camera like `galaxy render` does and write it as the next frame
+#[allow(clippy::too_many_arguments)]
+pub fn record_frame(
+    windows: Res<Windows>,
+    stars: Query<(&StarComponent, &KindComponent, &ObjectIndex)>,
+    galaxy_settings: Res<GalaxySettingsResource>,
+    sim_time: Res<SimulationTime>,
+    camera_path: Res<CameraPathRecorder>,
+    mut recorder: ResMut<FrameRecorder>,
+    cam_query: Query<(&Transform, &OrthographicProjection, Option<&BloomSettings>), With<PanCam>>,
+) {
+    let Some(recording) = recorder.recording.as_mut() else {
+        return;
+    };
+    let (Some(window), Ok((transform, projection, bloom))) =
+        (windows.get_primary(), cam_query.get_single())
+    else {
+        return;
+    };
+
+    let view = View {
+        width: window.width().round() as u32,
+        height: window.height().round() as u32,
+        center: transform.translation.truncate(),
+        scale: projection.scale,
+        years: sim_time.years,
+    };
+    let bloom = bloom.map(|bloom| Bloom {
+        threshold: bloom.threshold,
+        knee: bloom.knee,
+        scale: bloom.scale,
+        intensity: bloom.intensity,
+    });
+    let bloom = bloom.as_ref().filter(|bloom| bloom.intensity > 0.);
+
+    let objects = star_component::galaxy_objects(stars.iter());
+    let image = render::render(
+        &objects,
+        &galaxy_settings,
+        &view,
+        bloom,
+        &recording.particle,
+    );
+    let path = frames::frame_path(&recording.dir, recording.frame);
+    let result = fs::create_dir_all(&recording.dir)
+        .map_err(|err| err.to_string())
+        .and_then(|()| image.save(&path).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        warn!("Unable to write frame {}: {}", path.display(), err);
+        recorder.stop();
+        recorder.status = format!("Recording failed: {}", err);
+        return;
+    }
+
+    recording.frame += 1;
+    let frame = recording.frame;
+    let is_path_done = recording.until_path_end && camera_path.state == CameraPathState::Idle;
+    recorder.status = format!("Rendering frame {} offline", frame);
+    if is_path_done {
+        recorder.stop();
+    }
+}
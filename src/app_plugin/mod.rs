@@ -2,29 +2,103 @@ use bevy::{prelude::*, winit::WinitSettings};
 
 use super::pan_cam;
 
-pub mod density_wave;
+pub use galaxy::{density_wave, galaxy_setting_component};
+
+mod bookmark_system;
+pub mod camera_bookmarks_resource;
+pub mod camera_path_resource;
+mod camera_path_system;
+pub mod catalog_export_resource;
+pub mod config_problems_resource;
 mod dust_fade_system;
-pub mod galaxy_setting_component;
+mod follow_system;
+pub mod frame_recorder_resource;
+mod frame_recorder_system;
 mod lod_setting_resource;
+mod picking_system;
+pub mod preset_resource;
+pub mod selected_star_resource;
+mod settings_asset_loader;
+mod settings_asset_system;
+mod settings_assets_resource;
+pub mod settings_resource;
 mod setup_system;
-mod star_component;
+pub mod simulation_time_resource;
+mod simulation_time_system;
+pub mod snapshot_resource;
+pub mod star_component;
+pub mod star_index_resource;
+mod star_index_system;
 mod stars_lod_system;
 mod update_color_system;
 mod update_stars_system;
 mod update_transform_system;
+pub mod view_options_resource;
 
-pub struct AppPlugin;
+#[derive(Default)]
+pub struct AppPlugin {
+    pub options: view_options_resource::ViewOptions,
+}
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
+        let load_problems = settings_asset_loader::LoadProblems::default();
+        let loaded_galaxy_settings = settings_asset_loader::LoadedGalaxySettings::default();
         app.insert_resource(ClearColor(Color::BLACK))
-            // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
-            .insert_resource(WinitSettings::desktop_app())
+            // Run continuously, stars keep moving along their orbits without user input
+            .insert_resource(WinitSettings::game())
+            .init_resource::<simulation_time_resource::SimulationTime>()
+            .init_resource::<simulation_time_resource::TimeControl>()
+            .init_resource::<preset_resource::PresetLibrary>()
+            .init_resource::<config_problems_resource::ConfigProblems>()
+            .init_resource::<catalog_export_resource::CatalogExport>()
+            .init_resource::<snapshot_resource::SnapshotLibrary>()
+            .init_resource::<selected_star_resource::SelectedStar>()
+            .init_resource::<star_index_resource::StarIndex>()
+            .init_resource::<camera_bookmarks_resource::CameraBookmarks>()
+            .init_resource::<camera_path_resource::CameraPathRecorder>()
+            .init_resource::<frame_recorder_resource::FrameRecorder>()
+            // Settings files are assets and are reloaded when edited
+            .add_asset::<settings_resource::GalaxySettingsResource>()
+            .add_asset::<settings_resource::DensityWaveResource>()
+            .add_asset_loader(settings_asset_loader::SettingsAssetLoader {
+                galaxy_settings_path: self.options.galaxy_settings_path.clone(),
+                density_wave_path: self.options.density_wave_path.clone(),
+                galaxy_settings: loaded_galaxy_settings.clone(),
+                problems: load_problems.clone(),
+            })
+            .insert_resource(load_problems)
+            .insert_resource(loaded_galaxy_settings)
+            .insert_resource(self.options.clone())
             .add_startup_system(setup_system::setup)
-            .add_system(update_stars_system::update_stars)
+            .add_system(simulation_time_system::update_time_control)
+            .add_system(
+                simulation_time_system::advance_simulation_time
+                    .after(simulation_time_system::update_time_control),
+            )
+            .add_system(settings_asset_system::apply_settings_assets)
+            .add_system(
+                update_stars_system::update_stars
+                    .after(settings_asset_system::apply_settings_assets),
+            )
             // Update transform and color if changed
             .add_system(update_transform_system::update_transform)
+            .add_system(
+                update_transform_system::update_position
+                    .after(simulation_time_system::advance_simulation_time),
+            )
             .add_system(update_color_system::update_color)
+            .add_system(picking_system::pick_star)
+            .add_system(follow_system::update_follow.after(picking_system::pick_star))
+            .add_system(bookmark_system::update_bookmark_keys)
+            .add_system(camera_path_system::update_camera_path_keys)
+            // Overrides the epoch the time control advanced to
+            .add_system(
+                camera_path_system::play_camera_path
+                    .after(camera_path_system::update_camera_path_keys)
+                    .after(simulation_time_system::advance_simulation_time)
+                    .before(update_transform_system::update_position),
+            )
             // Update bloom while zooming
             .add_system_to_stage(
                 CoreStage::PostUpdate,
@@ -34,6 +108,13 @@ impl Plugin for AppPlugin {
                 CoreStage::PostUpdate,
                 stars_lod_system::update_other_visibility,
             )
-            .add_system(dust_fade_system::update_dust_fade);
+            .add_system(dust_fade_system::update_dust_fade)
+            .add_system_to_stage(CoreStage::PostUpdate, star_index_system::update_star_index)
+            .add_system(frame_recorder_system::update_frame_recorder_keys)
+            // The camera is where the following moved it
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                frame_recorder_system::record_frame.after(pan_cam::camera_follow),
+            );
     }
 }
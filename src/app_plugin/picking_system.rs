@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use super::pan_cam::PanCam;
+use super::selected_star_resource::SelectedStar;
+use super::star_component::StarComponent;
+use super::star_index_resource::StarIndex;
+
+// Further than this from the press it is a drag of the camera, not a click
+const CLICK_DISTANCE_PX: f32 = 4.;
+// Tiny sprites can still be hit this close to their centre
+const MIN_PICK_RADIUS_PX: f32 = 6.;
+// Faded dust and H2 regions can't be picked
+const MIN_PICK_ALPHA: f32 = 0.05;
+
+// Select the visible object under the cursor on a left click
+#[allow(clippy::too_many_arguments)]
+pub fn pick_star(
+    windows: Res<Windows>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    keyboard: Res<Input<KeyCode>>,
+    camera_query: Query<(&PanCam, &Transform, &OrthographicProjection)>,
+    star_query: Query<(Entity, &Transform, &Sprite, &Visibility), With<StarComponent>>,
+    mut index: ResMut<StarIndex>,
+    mut selected: ResMut<SelectedStar>,
+    mut press_pos: Local<Option<Vec2>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        selected.entity = None;
+    }
+
+    let Some(window) = windows.get_primary() else {
+        return;
+    };
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+    let Ok((cam, cam_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    // Clicks on the UI disable the camera
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        *press_pos = cam.enabled.then_some(cursor_pos);
+    }
+    if !mouse_buttons.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(pressed_at) = press_pos.take() else {
+        return;
+    };
+    if pressed_at.distance(cursor_pos) > CLICK_DISTANCE_PX {
+        return;
+    }
+
+    let window_size = Vec2::new(window.width(), window.height());
+    let normalized_pos = (cursor_pos / window_size) * 2. - Vec2::ONE;
+    let proj_size = Vec2::new(projection.right, projection.top);
+    let cursor_offset = normalized_pos * proj_size * projection.scale;
+    let world_pos =
+        (cam_transform.translation + cam_transform.rotation * cursor_offset.extend(0.)).truncate();
+    let min_radius = MIN_PICK_RADIUS_PX * projection.scale;
+
+    let (grid, max_radius) = index.fresh(
+        star_query
+            .iter()
+            .map(|(entity, transform, sprite, _)| (entity, transform, sprite)),
+    );
+
+    // Only objects close enough to be hit are looked at
+    let reach = min_radius.max(max_radius);
+    selected.entity = grid
+        .query_radius(world_pos, reach)
+        .filter_map(|(_, entity)| {
+            let (_, transform, sprite, visibility) = star_query.get(entity).ok()?;
+            if !visibility.is_visible || sprite.color.a() < MIN_PICK_ALPHA {
+                return None;
+            }
+            let distance = transform.translation.truncate().distance(world_pos);
+            let radius = sprite
+                .custom_size
+                .map_or(0., |size| size.max_element() / 2.)
+                .max(min_radius);
+            (distance <= radius).then_some((entity, distance))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity);
+}
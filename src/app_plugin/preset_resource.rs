@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use galaxy::bookmark::CameraBookmark;
+use galaxy::preset::{self, Preset};
+use galaxy::validation::ConfigProblem;
+use std::path::PathBuf;
+
+use super::{density_wave::DensityWave, galaxy_setting_component::GalaxySettings};
+
+/// Presets in the presets directory and the one currently in use
+#[derive(Resource)]
+pub struct PresetLibrary {
+    pub dir: PathBuf,
+    pub current: Option<String>,
+    pub names: Vec<String>,
+    pub status: String,
+}
+
+impl Default for PresetLibrary {
+    fn default() -> Self {
+        let dir = PathBuf::from(preset::PRESETS_DIR);
+        let names = preset::list_presets(&dir);
+        Self {
+            dir,
+            current: None,
+            names,
+            status: String::new(),
+        }
+    }
+}
+
+impl PresetLibrary {
+    pub fn refresh(&mut self) {
+        self.names = preset::list_presets(&self.dir);
+    }
+
+    /// Save under the current name, or a new one if there is none yet
+    pub fn save(
+        &mut self,
+        galaxy_settings: &GalaxySettings,
+        density_wave: &DensityWave,
+        bookmarks: &[CameraBookmark],
+    ) {
+        match self.current.clone() {
+            Some(name) => self.write(&name, galaxy_settings, density_wave, bookmarks),
+            None => {
+                let name = preset::next_free_name(&self.dir);
+                self.write(&name, galaxy_settings, density_wave, bookmarks)
+            }
+        }
+    }
+
+    /// Save under a new name, an existing preset is not overwritten
+    pub fn save_as(
+        &mut self,
+        name: &str,
+        galaxy_settings: &GalaxySettings,
+        density_wave: &DensityWave,
+        bookmarks: &[CameraBookmark],
+    ) {
+        if preset::preset_path(&self.dir, name).exists() {
+            self.status = format!("Save failed: {} already exists", name);
+            return;
+        }
+        self.write(name, galaxy_settings, density_wave, bookmarks);
+    }
+
+    fn write(
+        &mut self,
+        name: &str,
+        galaxy_settings: &GalaxySettings,
+        density_wave: &DensityWave,
+        bookmarks: &[CameraBookmark],
+    ) {
+        let path = preset::preset_path(&self.dir, name);
+        let preset = Preset {
+            galaxy_settings: galaxy_settings.clone(),
+            density_wave: density_wave.clone(),
+            bookmarks: bookmarks.to_vec(),
+        };
+        match preset.save(&path) {
+            Ok(()) => {
+                info!("Preset {} is saved", path.display());
+                self.current = Some(name.to_string());
+                self.status = format!("Saved {}", name);
+            }
+            Err(err) => {
+                warn!("Unable to save preset {}: {}", path.display(), err);
+                self.status = format!("Save failed: {}", err);
+            }
+        }
+        self.refresh();
+    }
+
+    pub fn rename(&mut self, to: &str) {
+        let Some(from) = self.current.clone() else {
+            self.status = "No preset to rename".to_string();
+            return;
+        };
+        match preset::rename_preset(&self.dir, &from, to) {
+            Ok(()) => {
+                info!("Preset {} is renamed to {}", from, to);
+                self.current = Some(to.to_string());
+                self.status = format!("Renamed {} to {}", from, to);
+            }
+            Err(err) => {
+                warn!("Unable to rename preset {}: {}", from, err);
+                self.status = format!("Rename failed: {}", err);
+            }
+        }
+        self.refresh();
+    }
+
+    /// Load a preset, fields that could not be used are returned as problems
+    pub fn load(&mut self, name: &str) -> Option<(Preset, Vec<ConfigProblem>)> {
+        let path = preset::preset_path(&self.dir, name);
+        match Preset::load(&path) {
+            Ok((preset, problems)) => {
+                info!("Preset {} is loaded", path.display());
+                self.current = Some(name.to_string());
+                self.status = if problems.is_empty() {
+                    format!("Loaded {}", name)
+                } else {
+                    format!("Loaded {} with {} problem(s)", name, problems.len())
+                };
+                Some((preset, problems))
+            }
+            Err(err) => {
+                warn!("Unable to load preset {}: {}", path.display(), err);
+                self.status = format!("Load failed: {}", err);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// Object picked with the mouse, shown in the inspector
+#[derive(Resource, Default)]
+pub struct SelectedStar {
+    pub entity: Option<Entity>,
+}
@@ -0,0 +1,72 @@
+use bevy::asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use galaxy::validation::{self, ConfigProblem};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use super::galaxy_setting_component::GalaxySettings;
+use super::settings_resource::{DensityWaveResource, GalaxySettingsResource};
+
+/// Path of a file and the problems found in it
+pub type FileProblems = (String, Vec<ConfigProblem>);
+
+/// Problems found by the loader, which runs outside of the ECS,
+/// waiting to be moved into `ConfigProblems`
+#[derive(Resource, Default, Clone)]
+pub struct LoadProblems(pub Arc<Mutex<Vec<FileProblems>>>);
+
+/// Galaxy settings in use, copied for the loader which runs outside of the ECS
+#[derive(Resource, Default, Clone)]
+pub struct LoadedGalaxySettings(pub Arc<Mutex<GalaxySettings>>);
+
+/// Reads the file at `galaxy_settings_path` as `GalaxySettings` and the one at
+/// `density_wave_path` as a built `DensityWave`. Both go through validation,
+/// so bad fields fall back to defaults.
+/// The density wave falls back to defaults for the galaxy in use.
+pub struct SettingsAssetLoader {
+    pub galaxy_settings_path: PathBuf,
+    pub density_wave_path: PathBuf,
+    pub galaxy_settings: LoadedGalaxySettings,
+    pub problems: LoadProblems,
+}
+
+impl AssetLoader for SettingsAssetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let path = load_context.path().to_path_buf();
+            if path != self.galaxy_settings_path && path != self.density_wave_path {
+                return Err(bevy::asset::Error::msg(format!(
+                    "{} is not a settings file",
+                    path.display()
+                )));
+            }
+            let json = std::str::from_utf8(bytes)?;
+            let problems = if path == self.galaxy_settings_path {
+                let (settings, problems) = validation::galaxy_settings_from_str(json);
+                load_context.set_default_asset(LoadedAsset::new(GalaxySettingsResource(settings)));
+                problems
+            } else {
+                let galaxy_settings = self.galaxy_settings.0.lock().unwrap().clone();
+                let (wave, problems) = validation::density_wave_from_str(json, &galaxy_settings);
+                load_context.set_default_asset(LoadedAsset::new(DensityWaveResource(wave)));
+                problems
+            };
+            self.problems
+                .0
+                .lock()
+                .unwrap()
+                .push((path.display().to_string(), problems));
+            Ok(())
+        })
+    }
+
+    // Bevy picks loaders by extension alone and the settings files can have any name,
+    // so json files that are not one of them are turned away by path in `load`
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use galaxy::validation::ConfigProblem;
+
+use super::config_problems_resource::ConfigProblems;
+use super::settings_asset_loader::{LoadProblems, LoadedGalaxySettings};
+use super::settings_assets_resource::SettingsAssets;
+use super::settings_resource::{DensityWaveResource, GalaxySettingsResource};
+use super::snapshot_resource::SnapshotLibrary;
+use super::view_options_resource::ViewOptions;
+
+// Copy loaded or edited settings files into the resources, with the command line
+// overrides applied. `update_stars` regenerates the galaxy through change detection
+#[allow(clippy::too_many_arguments)]
+pub fn apply_settings_assets(
+    asset_server: Res<AssetServer>,
+    options: Res<ViewOptions>,
+    mut settings_assets: ResMut<SettingsAssets>,
+    mut galaxy_events: EventReader<AssetEvent<GalaxySettingsResource>>,
+    mut wave_events: EventReader<AssetEvent<DensityWaveResource>>,
+    galaxy_assets: Res<Assets<GalaxySettingsResource>>,
+    wave_assets: Res<Assets<DensityWaveResource>>,
+    mut galaxy_settings: ResMut<GalaxySettingsResource>,
+    mut density_wave: ResMut<DensityWaveResource>,
+    load_problems: Res<LoadProblems>,
+    loaded_galaxy_settings: Res<LoadedGalaxySettings>,
+    mut config_problems: ResMut<ConfigProblems>,
+    snapshots: Res<SnapshotLibrary>,
+) {
+    for (path, problems) in load_problems.0.lock().unwrap().drain(..) {
+        config_problems.report(&path, problems);
+    }
+
+    // A snapshot waiting to be shown wins over the files loaded with it
+    let is_snapshot_pending = snapshots.pending.is_some();
+
+    for event in galaxy_events.iter() {
+        if is_snapshot_pending {
+            continue;
+        }
+        if let AssetEvent::Created { handle } | AssetEvent::Modified { handle } = event {
+            if let Some(settings) = galaxy_assets.get(handle) {
+                let mut settings = settings.clone();
+                let problems = options.overrides.apply_to_galaxy(&mut settings);
+                config_problems.report("command line", problems);
+                if *galaxy_settings != settings {
+                    info!(
+                        "Setting {} is loaded",
+                        options.galaxy_settings_path.display()
+                    );
+                    *galaxy_settings = settings;
+                }
+            }
+        }
+    }
+
+    // Density wave files read from now on fall back to defaults for this galaxy
+    if galaxy_settings.is_changed() {
+        *loaded_galaxy_settings.0.lock().unwrap() = galaxy_settings.0.clone();
+    }
+
+    for event in wave_events.iter() {
+        if is_snapshot_pending {
+            continue;
+        }
+        if let AssetEvent::Created { handle } | AssetEvent::Modified { handle } = event {
+            if let Some(wave) = wave_assets.get(handle) {
+                let mut wave = wave.clone();
+                let problems = options.overrides.apply_to_wave(&mut wave, &galaxy_settings);
+                config_problems.report("command line (wave)", problems);
+                if *density_wave != wave {
+                    info!("Setting {} is loaded", options.density_wave_path.display());
+                    *density_wave = wave;
+                }
+            }
+        }
+    }
+
+    if settings_assets.is_loaded {
+        return;
+    }
+    let galaxy_state = asset_server.get_load_state(&settings_assets.galaxy_settings);
+    let wave_state = asset_server.get_load_state(&settings_assets.density_wave);
+    let is_done = |state| matches!(state, LoadState::Loaded | LoadState::Failed);
+    if !is_done(galaxy_state) || !is_done(wave_state) {
+        return;
+    }
+
+    for (state, path) in [
+        (galaxy_state, &options.galaxy_settings_path),
+        (wave_state, &options.density_wave_path),
+    ] {
+        if state == LoadState::Failed {
+            let problem = ConfigProblem::new("", "unable to load file, using defaults");
+            config_problems.report(&path.display().to_string(), vec![problem]);
+        }
+    }
+    settings_assets.is_loaded = true;
+    // Generate even if the files hold exactly the defaults
+    galaxy_settings.set_changed();
+}
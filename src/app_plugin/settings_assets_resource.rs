@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+use super::settings_resource::{DensityWaveResource, GalaxySettingsResource};
+
+/// Handles of the settings files, kept alive so edits are hot-reloaded
+#[derive(Resource)]
+pub struct SettingsAssets {
+    pub galaxy_settings: Handle<GalaxySettingsResource>,
+    pub density_wave: Handle<DensityWaveResource>,
+    /// Both files were loaded or failed to load, the galaxy can be generated
+    pub is_loaded: bool,
+}
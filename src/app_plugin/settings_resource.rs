@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+
+use super::{density_wave::DensityWave, galaxy_setting_component::GalaxySettings};
+
+/// Settings of the shown galaxy. Also an asset, so `galaxy_settings.json`
+/// can be reloaded while running
+#[derive(Resource, TypeUuid, Clone, PartialEq, Deref, DerefMut)]
+#[uuid = "c17dc26d-88ea-4b35-86e9-9192309a7288"]
+pub struct GalaxySettingsResource(pub GalaxySettings);
+
+/// Density wave of the shown galaxy. Also an asset, so `density_wave.json`
+/// can be reloaded while running
+#[derive(Resource, TypeUuid, Clone, PartialEq, Deref, DerefMut)]
+#[uuid = "bdf1d442-917b-41c7-b12b-886053ae226f"]
+pub struct DensityWaveResource(pub DensityWave);
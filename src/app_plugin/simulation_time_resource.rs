@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+/// Simulated galactic time. `years` is the epoch used to place every star on its orbit.
+#[derive(Resource, Default)]
+pub struct SimulationTime {
+    pub years: f64,
+}
+
+impl SimulationTime {
+    /// Jump to an absolute epoch
+    pub fn seek(&mut self, years: f64) {
+        self.years = years;
+    }
+}
+
+/// How the simulated time advances with the real time
+#[derive(Resource)]
+pub struct TimeControl {
+    pub is_paused: bool,
+    pub is_reversed: bool,
+    pub years_per_second: f64,
+    pub min_years_per_second: f64,
+    pub max_years_per_second: f64,
+}
+
+impl TimeControl {
+    const RATE_STEP: f64 = 2.;
+
+    /// Signed rate in years per real second, zero while paused
+    pub fn rate(&self) -> f64 {
+        if self.is_paused {
+            0.
+        } else if self.is_reversed {
+            -self.years_per_second
+        } else {
+            self.years_per_second
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.is_paused = !self.is_paused;
+    }
+
+    pub fn toggle_reverse(&mut self) {
+        self.is_reversed = !self.is_reversed;
+    }
+
+    pub fn faster(&mut self) {
+        self.years_per_second =
+            (self.years_per_second * TimeControl::RATE_STEP).min(self.max_years_per_second);
+    }
+
+    pub fn slower(&mut self) {
+        self.years_per_second =
+            (self.years_per_second / TimeControl::RATE_STEP).max(self.min_years_per_second);
+    }
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        Self {
+            is_paused: false,
+            is_reversed: false,
+            years_per_second: 1_000_000.,
+            min_years_per_second: 1_000.,
+            max_years_per_second: 1_000_000_000.,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use super::frame_recorder_resource::FrameRecorder;
+use super::simulation_time_resource::{SimulationTime, TimeControl};
+
+pub fn advance_simulation_time(
+    time: Res<Time>,
+    time_control: Res<TimeControl>,
+    frame_recorder: Res<FrameRecorder>,
+    mut sim_time: ResMut<SimulationTime>,
+) {
+    let delta = frame_recorder.step_seconds(&time) * time_control.rate();
+    if delta != 0. {
+        sim_time.years += delta;
+    }
+}
+
+// Space: pause/resume, [ and ]: slower/faster, R: reverse, Home: back to epoch 0
+pub fn update_time_control(
+    keyboard: Res<Input<KeyCode>>,
+    mut time_control: ResMut<TimeControl>,
+    mut sim_time: ResMut<SimulationTime>,
+) {
+    if keyboard.just_pressed(KeyCode::Space) {
+        time_control.toggle_pause();
+    }
+    if keyboard.just_pressed(KeyCode::RBracket) {
+        time_control.faster();
+    }
+    if keyboard.just_pressed(KeyCode::LBracket) {
+        time_control.slower();
+    }
+    if keyboard.just_pressed(KeyCode::R) {
+        time_control.toggle_reverse();
+    }
+    if keyboard.just_pressed(KeyCode::Home) {
+        sim_time.seek(0.);
+    }
+}
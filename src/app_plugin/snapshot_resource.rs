@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use galaxy::snapshot::{self, Snapshot};
+use std::path::PathBuf;
+
+/// Snapshots in the snapshots directory
+#[derive(Resource)]
+pub struct SnapshotLibrary {
+    pub dir: PathBuf,
+    pub names: Vec<String>,
+    pub status: String,
+    /// Loaded snapshot, its objects are spawned instead of generated ones
+    pub pending: Option<Snapshot>,
+}
+
+impl Default for SnapshotLibrary {
+    fn default() -> Self {
+        let dir = PathBuf::from(snapshot::SNAPSHOTS_DIR);
+        let names = snapshot::list_snapshots(&dir);
+        Self {
+            dir,
+            names,
+            status: String::new(),
+            pending: None,
+        }
+    }
+}
+
+impl SnapshotLibrary {
+    pub fn refresh(&mut self) {
+        self.names = snapshot::list_snapshots(&self.dir);
+    }
+
+    /// Save under a new name
+    pub fn save(&mut self, snapshot: &Snapshot) {
+        let name = snapshot::next_free_name(&self.dir);
+        let path = snapshot::snapshot_path(&self.dir, &name);
+        match snapshot.save(&path) {
+            Ok(()) => {
+                info!("Snapshot {} is saved", path.display());
+                self.status = format!("Saved {}", name);
+            }
+            Err(err) => {
+                warn!("Unable to save snapshot {}: {}", path.display(), err);
+                self.status = format!("Save failed: {}", err);
+            }
+        }
+        self.refresh();
+    }
+
+    pub fn load(&mut self, name: &str) -> Option<Snapshot> {
+        let path = snapshot::snapshot_path(&self.dir, name);
+        match Snapshot::load(&path) {
+            Ok(snapshot) => {
+                info!("Snapshot {} is loaded", path.display());
+                self.status = format!("Loaded {}", name);
+                Some(snapshot)
+            }
+            Err(err) => {
+                warn!("Unable to load snapshot {}: {}", path.display(), err);
+                self.status = format!("Load failed: {}", err);
+                None
+            }
+        }
+    }
+}
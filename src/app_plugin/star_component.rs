@@ -1,9 +1,18 @@
 use bevy::prelude::*;
-use std::f32;
+use galaxy::generator::GalaxyObject;
+use galaxy::star::{ObjectKind, Star};
+
+/// Orbit and appearance of the object, as generated
+#[derive(Component, Debug, Default, Clone, Deref, DerefMut)]
+pub struct StarComponent(pub Star);
+
+/// What the object is, as generated
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Deref)]
+pub struct KindComponent(pub ObjectKind);
 
 #[derive(Bundle, Clone)]
 pub struct StarSpriteBundle {
-    pub star: Star,
+    pub star: StarComponent,
     pub view: SpriteBundle,
 }
 
@@ -16,29 +25,36 @@ impl Default for StarSpriteBundle {
     }
 }
 
-#[derive(Component, Debug, Default, Clone, Reflect)]
-#[reflect(Component)]
-pub struct Star {
-    pub theta0: f32,     // initial angular position on the ellipse
-    pub vel_theta: f32,  // angular velocity
-    pub tilt_angle: f32, // tilt angle of the ellipse
-    pub a: f32,          // semi-minor axes
-    pub b: f32,          // semi-major axes
-    pub temp: f32,       // star temperature
-    pub mag: f32,        // brightness;
-}
-
 #[derive(Component, Deref)]
 pub struct Alpha(pub f32);
 
 // Type 0:star, 1:dust, 2 and 3: h2 regions
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct StarTag;
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct DustTag;
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct DustFilamentsTag;
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct H2Tag;
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct H2CoreTag;
+
+/// Position of the object in the generated list of its kind
+#[derive(Component, Clone, Copy)]
+pub struct ObjectIndex(pub usize);
+
+/// Objects of the entities in the order of the generator
+pub fn galaxy_objects<'a>(
+    stars: impl Iterator<Item = (&'a StarComponent, &'a KindComponent, &'a ObjectIndex)>,
+) -> Vec<GalaxyObject> {
+    let mut stars: Vec<_> = stars.collect();
+    stars.sort_by_key(|(_, kind, index)| (kind.0 as usize, index.0));
+    stars
+        .into_iter()
+        .map(|(star, kind, _)| GalaxyObject {
+            kind: **kind,
+            star: star.0.clone(),
+        })
+        .collect()
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use galaxy::spatial_index::SpatialGrid;
+
+/// Grid cell size in parsec
+const CELL_SIZE: f32 = 250.;
+
+/// Positions of all objects, rebuilt when a pick needs them after they moved
+#[derive(Resource)]
+pub struct StarIndex {
+    grid: SpatialGrid<Entity>,
+    /// Half size of the largest sprite, how far from its position an object can be hit
+    max_radius: f32,
+    /// Objects moved, spawned or despawned since the last rebuild
+    is_stale: bool,
+}
+
+impl Default for StarIndex {
+    fn default() -> Self {
+        Self {
+            grid: SpatialGrid::new(CELL_SIZE),
+            max_radius: 0.,
+            is_stale: true,
+        }
+    }
+}
+
+impl StarIndex {
+    pub fn is_stale(&self) -> bool {
+        self.is_stale
+    }
+
+    pub fn mark_stale(&mut self) {
+        self.is_stale = true;
+    }
+
+    /// Grid of the objects and the largest hit radius, rebuilt from the objects first
+    /// if they changed since. The objects are only read when it is rebuilt.
+    pub fn fresh<'a>(
+        &mut self,
+        objects: impl IntoIterator<Item = (Entity, &'a Transform, &'a Sprite)>,
+    ) -> (&SpatialGrid<Entity>, f32) {
+        if self.is_stale {
+            self.rebuild(objects);
+        }
+        (&self.grid, self.max_radius)
+    }
+
+    fn rebuild<'a>(
+        &mut self,
+        objects: impl IntoIterator<Item = (Entity, &'a Transform, &'a Sprite)>,
+    ) {
+        self.grid.clear();
+        self.max_radius = 0.;
+        for (entity, transform, sprite) in objects {
+            self.grid.insert(transform.translation.truncate(), entity);
+            if let Some(size) = sprite.custom_size {
+                self.max_radius = self.max_radius.max(size.max_element() / 2.);
+            }
+        }
+        self.is_stale = false;
+    }
+}
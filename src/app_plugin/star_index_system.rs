@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use super::simulation_time_resource::SimulationTime;
+use super::star_component::StarComponent;
+use super::star_index_resource::StarIndex;
+
+// Runs after the update stage, when moved, spawned and despawned objects are all applied.
+// Only marks the index, it is rebuilt by the next pick instead of every frame.
+pub fn update_star_index(
+    mut index: ResMut<StarIndex>,
+    sim_time: Res<SimulationTime>,
+    moved_query: Query<(), (With<StarComponent>, Changed<Transform>)>,
+    removed: RemovedComponents<StarComponent>,
+) {
+    if index.is_stale() {
+        return;
+    }
+    let is_moved = sim_time.is_changed() || !moved_query.is_empty();
+    if is_moved || removed.iter().next().is_some() {
+        index.mark_stale();
+    }
+}
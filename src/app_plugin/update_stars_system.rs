@@ -1,327 +1,271 @@
 use bevy::prelude::*;
-use rand::prelude::*;
+use galaxy::generator;
+use galaxy::star::{ObjectKind, Star};
 
-use super::star_component::Star;
+use super::settings_resource::{DensityWaveResource, GalaxySettingsResource};
+use super::snapshot_resource::SnapshotLibrary;
+use super::star_component::{KindComponent, StarComponent};
 use super::{density_wave, galaxy_setting_component};
-use super::{lod_setting_resource, star_component};
+use super::{lod_setting_resource, settings_assets_resource, star_component};
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_stars(
     mut commands: Commands,
-    galaxy_setting: Res<galaxy_setting_component::GalaxySettings>,
-    density_wave: Res<density_wave::DensityWave>,
-    mut stars: Query<Entity, With<star_component::Star>>,
+    galaxy_setting: Res<GalaxySettingsResource>,
+    density_wave: Res<DensityWaveResource>,
+    stars: Query<(Entity, &KindComponent, &star_component::ObjectIndex)>,
     assets: Res<AssetServer>,
     lod_settings: Res<lod_setting_resource::LodSetting>,
+    settings_assets: Res<settings_assets_resource::SettingsAssets>,
+    mut snapshots: ResMut<SnapshotLibrary>,
+    mut previous_setting: Local<Option<galaxy_setting_component::GalaxySettings>>,
+    mut is_snapshot_shown: Local<bool>,
 ) {
-    if !galaxy_setting.is_changed() && !density_wave.is_changed() {
+    // Wait for the settings files, not to generate the defaults first
+    if !settings_assets.is_loaded {
         return;
     }
+    if snapshots.pending.is_none() && !galaxy_setting.is_changed() && !density_wave.is_changed() {
+        return;
+    }
+
+    let sprite_handle: Handle<Image> = assets.load("particle.png");
 
-    if !stars.is_empty() {
-        // delete all
-        for star_entity in &mut stars {
+    // Spawn the objects of a loaded snapshot as they are saved
+    if let Some(snapshot) = snapshots.pending.take() {
+        for (star_entity, _, _) in &stars {
             commands.entity(star_entity).despawn();
         }
+        for kind in ObjectKind::ALL {
+            let objects = snapshot
+                .objects
+                .iter()
+                .filter(|object| object.kind == kind)
+                .map(|object| object.star.clone())
+                .collect();
+            spawn_objects(
+                &mut commands,
+                kind,
+                0,
+                objects,
+                &sprite_handle,
+                &lod_settings,
+            );
+        }
+        *previous_setting = Some(galaxy_setting.0.clone());
+        *is_snapshot_shown = true;
+        return;
     }
 
-    create_all_objects(
-        &mut commands,
-        galaxy_setting,
-        density_wave,
-        assets.load("particle.png"),
-        &lod_settings,
-    );
-}
-
-fn create_all_objects(
-    commands: &mut Commands,
-    galaxy_setting: Res<galaxy_setting_component::GalaxySettings>,
-    density_wave: Res<density_wave::DensityWave>,
-    sprite_handle: Handle<Image>,
-    lod_settings: &Res<lod_setting_resource::LodSetting>,
-) {
-    let mut rnd = StdRng::seed_from_u64(galaxy_setting.seed);
-
-    commands.spawn_batch(create_dusts(
-        &galaxy_setting,
-        &density_wave,
-        &sprite_handle,
-        &mut rnd,
-        lod_settings.is_other_visibile,
-    ));
-
-    commands.spawn_batch(create_dusts_filaments(
-        &galaxy_setting,
-        &sprite_handle,
-        &mut rnd,
-        lod_settings.is_other_visibile,
-    ));
-
-    commands.spawn_batch(create_h2(
-        &galaxy_setting,
-        &sprite_handle,
-        &mut rnd,
-        lod_settings.is_other_visibile,
-    ));
-    commands.spawn_batch(create_h2_core(
-        &galaxy_setting,
-        &sprite_handle,
-        &mut rnd,
-        lod_settings.is_other_visibile,
-    ));
-
-    commands.spawn_batch(create_stars(
-        &galaxy_setting,
-        &density_wave,
-        &sprite_handle,
-        &mut rnd,
-        lod_settings.is_stars_visibile,
-    ));
-}
-
-fn create_h2(
-    galaxy_setting: &Res<galaxy_setting_component::GalaxySettings>,
-    sprite_handle: &Handle<Image>,
-    rnd: &mut StdRng,
-    is_visibile: bool,
-) -> Vec<(star_component::H2Tag, star_component::StarSpriteBundle)> {
-    let mut stars: Vec<(star_component::H2Tag, star_component::StarSpriteBundle)> = vec![];
-    for _i in 0..galaxy_setting.count_h2 {
-        let x: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
-        let y: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
-        let rad = f32::sqrt(x * x + y * y);
-
-        let temp = 6000. + (6000. * rnd.gen::<f32>() - 3000.);
-        let mag = 0.1 + 0.05 * rnd.gen::<f32>();
-        let b = rad * galaxy_setting.get_excentricity(rad);
-        let star_sprite = star_component::StarSpriteBundle {
-            star: Star {
-                theta0: 360.0 * rnd.gen::<f32>(),
-                vel_theta: galaxy_setting.get_orbital_velocity((rad + b) / 2.),
-                tilt_angle: galaxy_setting.get_angular_offset(rad),
-                a: rad,
-                b: b,
-                temp: temp,
-                mag: mag,
-            },
-            view: SpriteBundle {
-                texture: sprite_handle.clone(),
-                visibility: Visibility {
-                    is_visible: is_visibile,
-                },
-                ..default()
-            },
-        };
-        stars.push((star_component::H2Tag, star_sprite));
+    // The objects of a snapshot are numbered per kind but not drawn like generated
+    // ones, so after a snapshot even a count change generates the whole galaxy
+    if *is_snapshot_shown {
+        let is_same = previous_setting.as_ref() == Some(&galaxy_setting.0);
+        if is_same && !density_wave.is_changed() {
+            return;
+        }
+        *is_snapshot_shown = false;
+        *previous_setting = None;
     }
 
-    stars
-}
-
-fn create_h2_core(
-    galaxy_setting: &Res<galaxy_setting_component::GalaxySettings>,
-    sprite_handle: &Handle<Image>,
-    rnd: &mut StdRng,
-    is_visibile: bool,
-) -> Vec<(star_component::H2CoreTag, star_component::StarSpriteBundle)> {
-    let mut stars: Vec<(star_component::H2CoreTag, star_component::StarSpriteBundle)> = vec![];
-    for _i in 0..galaxy_setting.count_h2 {
-        let x: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
-        let y: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
-        let rad = f32::sqrt(x * x + y * y);
+    match previous_setting.as_ref() {
+        // Only counts changed: spawn or despawn the difference of the changed kinds
+        Some(previous)
+            if !density_wave.is_changed() && previous.differs_only_in_counts(&galaxy_setting) =>
+        {
+            for kind in ObjectKind::ALL {
+                update_objects_count(
+                    &mut commands,
+                    kind,
+                    previous,
+                    &galaxy_setting,
+                    &density_wave,
+                    &stars,
+                    &sprite_handle,
+                    &lod_settings,
+                );
+            }
+        }
+        _ => {
+            // delete all
+            for (star_entity, _, _) in &stars {
+                commands.entity(star_entity).despawn();
+            }
 
-        let temp = 6000. + (6000. * rnd.gen::<f32>() - 3000.);
-        let mag = 0.1 + 0.05 * rnd.gen::<f32>();
-        let b = rad * galaxy_setting.get_excentricity(rad);
-        let star_sprite = star_component::StarSpriteBundle {
-            star: Star {
-                theta0: 360.0 * rnd.gen::<f32>(),
-                vel_theta: galaxy_setting.get_orbital_velocity((rad + b) / 2.),
-                tilt_angle: galaxy_setting.get_angular_offset(rad),
-                a: rad,
-                b: b,
-                temp: temp,
-                mag: mag,
-            },
-            view: SpriteBundle {
-                texture: sprite_handle.clone(),
-                visibility: Visibility {
-                    is_visible: is_visibile,
-                },
-                ..default()
-            },
-        };
-        stars.push((star_component::H2CoreTag, star_sprite));
+            for kind in ObjectKind::ALL {
+                let objects = generator::create_objects(
+                    kind,
+                    &galaxy_setting,
+                    &density_wave,
+                    galaxy_setting.seed,
+                );
+                spawn_objects(
+                    &mut commands,
+                    kind,
+                    0,
+                    objects,
+                    &sprite_handle,
+                    &lod_settings,
+                );
+            }
+        }
     }
 
-    stars
+    *previous_setting = Some(galaxy_setting.0.clone());
 }
 
-fn create_stars(
-    galaxy_setting: &Res<galaxy_setting_component::GalaxySettings>,
-    density_wave: &Res<density_wave::DensityWave>,
+/// Generates only the objects at the end that were added, and again the ones
+/// that got brighter or dimmer as the star count changed
+#[allow(clippy::too_many_arguments)]
+fn update_objects_count(
+    commands: &mut Commands,
+    kind: ObjectKind,
+    previous: &galaxy_setting_component::GalaxySettings,
+    galaxy_setting: &galaxy_setting_component::GalaxySettings,
+    density_wave: &density_wave::DensityWave,
+    stars: &Query<(Entity, &KindComponent, &star_component::ObjectIndex)>,
     sprite_handle: &Handle<Image>,
-    rnd: &mut StdRng,
-    is_visibile: bool,
-) -> Vec<(star_component::StarTag, star_component::StarSpriteBundle)> {
-    let mut temp = 6000.;
-    let mut mag = 1.0;
-    let mut stars: Vec<(star_component::StarTag, star_component::StarSpriteBundle)> = vec![];
-    // First star ist the black hole at the centre
-    let star = star_component::StarSpriteBundle {
-        star: Star {
-            theta0: 0.,
-            vel_theta: 0.,
-            tilt_angle: 0.,
-            a: 0.,
-            b: 0.,
-            temp: temp,
-            mag: mag,
-        },
-        view: SpriteBundle {
-            visibility: Visibility {
-                is_visible: is_visibile,
-            },
-            texture: sprite_handle.clone(),
-            ..default()
-        },
-    };
-    stars.push((star_component::StarTag, star));
-
-    // Initialize stars
-    for i in 1..galaxy_setting.count_stars {
-        let rad: f32 = density_wave.val_from_prob(rnd.gen());
-        temp = (4000. * rnd.gen::<f32>() - 2000.) + 6000.;
-        mag = 0.1 + 0.4 * rnd.gen::<f32>();
-        let mut star_sprite = star_component::StarSpriteBundle {
-            star: Star {
-                theta0: 360.0 * rnd.gen::<f32>(),
-                vel_theta: galaxy_setting.get_orbital_velocity(rad),
-                tilt_angle: galaxy_setting.get_angular_offset(rad),
-                a: rad,
-                b: rad * galaxy_setting.get_excentricity(rad),
-                temp: temp,
-                mag: mag,
-            },
-            view: SpriteBundle {
-                texture: sprite_handle.clone(),
-                visibility: Visibility {
-                    is_visible: is_visibile,
-                },
-                ..default()
-            },
-        };
+    lod_settings: &lod_setting_resource::LodSetting,
+) {
+    let seed = galaxy_setting.seed;
+    let count = generator::object_count(kind, galaxy_setting, seed);
+    let (previous_bright, bright) = (
+        generator::bright_count(kind, previous),
+        generator::bright_count(kind, galaxy_setting),
+    );
+    let changed_brightness = previous_bright.min(bright)..previous_bright.max(bright);
 
-        // Make a small portion of the stars brighter
-        if i < galaxy_setting.count_stars / 60 {
-            star_sprite.star.mag = 1_f32.min(star_sprite.star.mag + 0.1 + rnd.gen::<f32>() * 0.4);
+    let mut existing = 0;
+    for (star_entity, star_kind, index) in stars {
+        if **star_kind != kind {
+            continue;
         }
+        existing += 1;
+        if index.0 >= count || changed_brightness.contains(&index.0) {
+            commands.entity(star_entity).despawn();
+        }
+    }
 
-        stars.push((star_component::StarTag, star_sprite));
+    let respawned = changed_brightness.start..changed_brightness.end.min(existing).min(count);
+    if !respawned.is_empty() {
+        let objects = generator::create_object_range(
+            kind,
+            galaxy_setting,
+            density_wave,
+            seed,
+            respawned.clone(),
+        );
+        spawn_objects(
+            commands,
+            kind,
+            respawned.start,
+            objects,
+            sprite_handle,
+            lod_settings,
+        );
+    }
+    if existing < count {
+        let added = generator::create_object_range(
+            kind,
+            galaxy_setting,
+            density_wave,
+            seed,
+            existing..count,
+        );
+        spawn_objects(commands, kind, existing, added, sprite_handle, lod_settings);
     }
-    stars
 }
 
-fn create_dusts(
-    galaxy_setting: &Res<galaxy_setting_component::GalaxySettings>,
-    density_wave: &Res<density_wave::DensityWave>,
+fn spawn_objects(
+    commands: &mut Commands,
+    kind: ObjectKind,
+    first_index: usize,
+    stars: Vec<Star>,
     sprite_handle: &Handle<Image>,
-    rnd: &mut StdRng,
-    is_visibile: bool,
-) -> Vec<(star_component::DustTag, star_component::StarSpriteBundle)> {
-    let mag = 0.02 + 0.15 * rnd.gen::<f32>();
-    let mut rad: f32;
-    let mut stars: Vec<(star_component::DustTag, star_component::StarSpriteBundle)> = vec![];
-
-    // Initialize dust
-    for i in 0..galaxy_setting.count_dusts {
-        if i % 2 == 0 {
-            rad = density_wave.val_from_prob(rnd.gen());
-        } else {
-            let x: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
-            let y: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
-            rad = f32::sqrt(x * x + y * y);
-        }
-
-        let temp = galaxy_setting.base_temp + rad / 4.5;
-        let b = rad * galaxy_setting.get_excentricity(rad);
-        let mut star_sprite = star_component::StarSpriteBundle {
-            star: Star {
-                theta0: 360.0 * rnd.gen::<f32>(),
-                vel_theta: galaxy_setting.get_orbital_velocity((rad + b) / 2.),
-                tilt_angle: galaxy_setting.get_angular_offset(rad),
-                a: rad,
-                b: rad * galaxy_setting.get_excentricity(rad),
-                temp,
-                mag,
-            },
-            view: SpriteBundle {
-                texture: sprite_handle.clone(),
-                visibility: Visibility {
-                    is_visible: is_visibile,
-                },
-                ..default()
-            },
-        };
-
-        // Make a small portion of the stars brighter
-        if i < galaxy_setting.count_stars / 60 {
-            star_sprite.star.mag = 1_f32.min(star_sprite.star.mag + 0.1 + rnd.gen::<f32>() * 0.4);
-        }
+    lod_settings: &lod_setting_resource::LodSetting,
+) {
+    let is_visibile = match kind {
+        ObjectKind::Star => lod_settings.is_stars_visibile,
+        _ => lod_settings.is_other_visibile,
+    };
 
-        stars.push((star_component::DustTag, star_sprite));
+    match kind {
+        ObjectKind::Star => commands.spawn_batch(to_sprite_bundles(
+            star_component::StarTag,
+            kind,
+            first_index,
+            stars,
+            sprite_handle,
+            is_visibile,
+        )),
+        ObjectKind::Dust => commands.spawn_batch(to_sprite_bundles(
+            star_component::DustTag,
+            kind,
+            first_index,
+            stars,
+            sprite_handle,
+            is_visibile,
+        )),
+        ObjectKind::DustFilament => commands.spawn_batch(to_sprite_bundles(
+            star_component::DustFilamentsTag,
+            kind,
+            first_index,
+            stars,
+            sprite_handle,
+            is_visibile,
+        )),
+        ObjectKind::H2 => commands.spawn_batch(to_sprite_bundles(
+            star_component::H2Tag,
+            kind,
+            first_index,
+            stars,
+            sprite_handle,
+            is_visibile,
+        )),
+        ObjectKind::H2Core => commands.spawn_batch(to_sprite_bundles(
+            star_component::H2CoreTag,
+            kind,
+            first_index,
+            stars,
+            sprite_handle,
+            is_visibile,
+        )),
     }
-    stars
 }
 
-fn create_dusts_filaments(
-    galaxy_setting: &Res<galaxy_setting_component::GalaxySettings>,
+fn to_sprite_bundles<T: Component + Copy>(
+    tag: T,
+    kind: ObjectKind,
+    first_index: usize,
+    stars: Vec<Star>,
     sprite_handle: &Handle<Image>,
-    rnd: &mut StdRng,
     is_visibile: bool,
 ) -> Vec<(
-    star_component::DustFilamentsTag,
+    T,
+    KindComponent,
+    star_component::ObjectIndex,
     star_component::StarSpriteBundle,
 )> {
-    const FACTOR: usize = 100;
-    let mut stars: Vec<(
-        star_component::DustFilamentsTag,
-        star_component::StarSpriteBundle,
-    )> = vec![];
-    for _i in 0..galaxy_setting.count_dusts_filaments / FACTOR {
-        {
-            let x: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
-            let y: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
-            let mut rad = f32::sqrt(x * x + y * y);
-
-            let theta = 360.0 * rnd.gen::<f32>();
-            let mag = 0.1 + 0.05 * rnd.gen::<f32>();
-            let temp = galaxy_setting.base_temp + rad / 4.5 - 1000.;
-            let b = rad * galaxy_setting.get_excentricity(rad);
-            let num = (FACTOR as f32 * rnd.gen::<f32>()) as usize;
-            for _j in 0..num {
-                rad = rad + 200. - 400. * rnd.gen::<f32>();
-                let star_sprite = star_component::StarSpriteBundle {
-                    star: Star {
-                        theta0: theta + 10. - 20. * rnd.gen::<f32>(),
-                        vel_theta: galaxy_setting.get_orbital_velocity((rad + b) / 2.),
-                        tilt_angle: galaxy_setting.get_angular_offset(rad),
-                        a: rad,
-                        b: rad * galaxy_setting.get_excentricity(rad),
-                        temp,
-                        mag: mag + 0.025 * rnd.gen::<f32>(),
-                    },
-                    view: SpriteBundle {
-                        texture: sprite_handle.clone(),
-                        visibility: Visibility {
-                            is_visible: is_visibile,
-                        },
-                        ..default()
-                    },
-                };
-                stars.push((star_component::DustFilamentsTag, star_sprite));
-            }
-        }
-    }
     stars
+        .into_iter()
+        .enumerate()
+        .map(|(i, star)| {
+            let star_sprite = star_component::StarSpriteBundle {
+                star: StarComponent(star),
+                view: SpriteBundle {
+                    texture: sprite_handle.clone(),
+                    visibility: Visibility {
+                        is_visible: is_visibile,
+                    },
+                    ..default()
+                },
+            };
+            (
+                tag,
+                KindComponent(kind),
+                star_component::ObjectIndex(first_index + i),
+                star_sprite,
+            )
+        })
+        .collect()
 }
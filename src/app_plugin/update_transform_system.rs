@@ -1,99 +1,46 @@
 use bevy::prelude::*;
+use galaxy::appearance;
+use galaxy::star::ObjectKind;
 
-use super::{
-    galaxy_setting_component,
-    star_component::{self, Star},
-};
+use super::settings_resource::GalaxySettingsResource;
+use super::simulation_time_resource::SimulationTime;
+use super::star_component::{KindComponent, StarComponent};
 
 pub fn update_transform(
     mut star_query: Query<
-        (
-            &Star,
-            &mut Transform,
-            &mut Sprite,
-            AnyOf<(
-                &star_component::StarTag,
-                &star_component::DustTag,
-                &star_component::DustFilamentsTag,
-                &star_component::H2Tag,
-                &star_component::H2CoreTag,
-            )>,
-        ),
+        (&StarComponent, &KindComponent, &mut Transform, &mut Sprite),
         Added<Transform>,
     >,
-    galaxy_setting: Res<galaxy_setting_component::GalaxySettings>,
+    galaxy_setting: Res<GalaxySettingsResource>,
+    sim_time: Res<SimulationTime>,
 ) {
-    for (star, mut transform, mut sprite, star_type) in &mut star_query {
-        let pos = calculate_position(
-            &galaxy_setting,
-            star.a,
-            star.b,
-            star.theta0,
-            star.vel_theta,
-            star.tilt_angle,
-        );
+    for (star, kind, mut transform, mut sprite) in &mut star_query {
+        let pos = star.position(&galaxy_setting, sim_time.years);
         transform.translation = pos.extend(0.);
 
-        let pos2 = calculate_position(
-            &galaxy_setting,
-            star.a + 1000.,
-            star.b,
-            star.theta0,
-            star.vel_theta,
-            star.tilt_angle,
-        );
-
-        if star_type.0.is_some() {
-            sprite.custom_size = Some(Vec2::ONE * star.mag * 4.0);
-        }
-
-        if star_type.1.is_some() {
-            sprite.custom_size = Some(Vec2::ONE * star.mag * 5.0 * galaxy_setting.dust_render_size);
-        }
-
-        if star_type.2.is_some() {
-            sprite.custom_size = Some(Vec2::ONE * star.mag * 2.0 * galaxy_setting.dust_render_size);
-        }
-
-        if star_type.3.is_some() {
-            sprite.custom_size =
-                Some(Vec2::ONE * (((1000.0 - Vec2::distance(pos, pos2)) / 10.) - 50.));
-        }
-
-        if star_type.4.is_some() {
-            sprite.custom_size =
-                Some(Vec2::ONE * 0.1 * (((1000.0 - Vec2::distance(pos, pos2)) / 10.) - 50.));
-        }
+        let size = appearance::object_size(**kind, star, &galaxy_setting, sim_time.years);
+        sprite.custom_size = Some(Vec2::ONE * size);
     }
 }
 
-const DEG_TO_RAD: f32 = 0.01745329251;
-fn calculate_position(
-    galaxy_setting: &Res<galaxy_setting_component::GalaxySettings>,
-    a: f32,
-    b: f32,
-    theta0: f32,
-    vel_theta: f32,
-    tilt_angle: f32,
-) -> Vec2 {
-    let theta_actual = theta0 + vel_theta;
-    let beta = -tilt_angle;
-    let alpha = theta_actual * DEG_TO_RAD;
-    let cosalpha = alpha.cos();
-    let sinalpha = alpha.sin();
-    let cosbeta = beta.cos();
-    let sinbeta = beta.sin();
+// Move every object along its orbit when the simulated time advances,
+// H2 regions also change size with the distance to their neighbour orbit
+pub fn update_position(
+    mut star_query: Query<(&StarComponent, &KindComponent, &mut Transform, &mut Sprite)>,
+    galaxy_setting: Res<GalaxySettingsResource>,
+    sim_time: Res<SimulationTime>,
+) {
+    if !sim_time.is_changed() {
+        return;
+    }
 
-    let mut pos = Vec2 {
-        x: (a * cosalpha * cosbeta - b * sinalpha * sinbeta),
-        y: (a * cosalpha * sinbeta + b * sinalpha * cosbeta),
-    };
+    for (star, kind, mut transform, mut sprite) in &mut star_query {
+        let pos = star.position(&galaxy_setting, sim_time.years);
+        transform.translation = pos.extend(transform.translation.z);
 
-    if galaxy_setting.pert_amp > 0 && galaxy_setting.pert_n > 0 {
-        pos.x += (a / galaxy_setting.pert_amp as f32)
-            * (alpha * 2.0 * galaxy_setting.pert_n as f32).sin();
-        pos.y += (a / galaxy_setting.pert_amp as f32)
-            * (alpha * 2.0 * galaxy_setting.pert_n as f32).cos();
+        if matches!(**kind, ObjectKind::H2 | ObjectKind::H2Core) {
+            let size = appearance::object_size(**kind, star, &galaxy_setting, sim_time.years);
+            sprite.custom_size = Some(Vec2::ONE * size);
+        }
     }
-    pos
 }
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use galaxy::overrides::Overrides;
+use galaxy::render::Bloom;
+use galaxy::snapshot::Snapshot;
+use std::path::PathBuf;
+
+/// How the application starts, set from the command line
+#[derive(Resource, Clone)]
+pub struct ViewOptions {
+    /// Asset paths of the settings files, they are hot-reloaded
+    pub galaxy_settings_path: PathBuf,
+    pub density_wave_path: PathBuf,
+    /// Applied on top of the settings files every time they are loaded
+    pub overrides: Overrides,
+    pub camera_scale: f32,
+    pub bloom: Bloom,
+    /// Camera glide and zoom easing rates, none to stop and zoom at once
+    pub pan_friction: Option<f32>,
+    pub zoom_smoothing: Option<f32>,
+    /// Shown instead of the settings files until they are edited, taken by the setup
+    pub snapshot: Option<Snapshot>,
+    /// Epoch to start at instead of the one of the snapshot
+    pub years: Option<f64>,
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        let camera_scale = 70.;
+        ViewOptions {
+            galaxy_settings_path: PathBuf::from("galaxy_settings.json"),
+            density_wave_path: PathBuf::from("density_wave.json"),
+            overrides: Overrides::default(),
+            camera_scale,
+            bloom: Bloom::for_scale(camera_scale),
+            pan_friction: Some(5.),
+            zoom_smoothing: Some(12.),
+            snapshot: None,
+            years: None,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use super::app_plugin::camera_bookmarks_resource::CameraBookmarks;
+use super::components::{BookmarkButton, BookmarkListTag};
+use super::pan_cam::PanCam;
+use super::setup_system::spawn_button;
+use super::text_entry_resource::{EntryTarget, TextEntry};
+
+pub fn update_bookmark_buttons(
+    button_query: Query<(&Interaction, &BookmarkButton), (Changed<Interaction>, With<Button>)>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut cam_query: Query<(&mut PanCam, &Transform, &OrthographicProjection)>,
+    mut entry: ResMut<TextEntry>,
+) {
+    for (interaction, button) in &button_query {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        for (mut cam, transform, projection) in &mut cam_query {
+            match *button {
+                BookmarkButton::Add => {
+                    let center = transform.translation.truncate();
+                    bookmarks.store(None, center, projection.scale);
+                }
+                BookmarkButton::FlyTo(index) => {
+                    if let Some(bookmark) = bookmarks.bookmarks.get(index) {
+                        cam.fly_to(bookmark.center(), bookmark.scale);
+                    }
+                }
+                BookmarkButton::Rename(index) => entry.start(EntryTarget::BookmarkName(index)),
+                BookmarkButton::Remove(index) => bookmarks.remove(index),
+            }
+        }
+    }
+
+    if let Some((EntryTarget::BookmarkName(index), text)) = entry.submitted.clone() {
+        entry.submitted = None;
+        bookmarks.rename(index, &text);
+    }
+}
+
+// Rebuild the bookmark list when bookmarks are added, removed or loaded with a preset
+pub fn update_bookmark_list(
+    mut commands: Commands,
+    bookmarks: Res<CameraBookmarks>,
+    asset_server: Res<AssetServer>,
+    list_query: Query<Entity, With<BookmarkListTag>>,
+    entry: Res<TextEntry>,
+) {
+    if !bookmarks.is_changed() && !entry.is_changed() {
+        return;
+    }
+
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    for list in &list_query {
+        commands.entity(list).despawn_descendants();
+        commands.entity(list).with_children(|parent| {
+            for (index, bookmark) in bookmarks.bookmarks.iter().enumerate() {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            ..default()
+                        },
+                        focus_policy: FocusPolicy::Pass,
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        // The first nine are on the number keys
+                        let name = if entry.is_editing(EntryTarget::BookmarkName(index)) {
+                            entry.display()
+                        } else {
+                            bookmark.name.clone()
+                        };
+                        let label = match index {
+                            0..=8 => format!("{} {}", index + 1, name),
+                            _ => name,
+                        };
+                        spawn_button(parent, &font, &label, BookmarkButton::FlyTo(index));
+                        spawn_button(parent, &font, "rename", BookmarkButton::Rename(index));
+                        spawn_button(parent, &font, "x", BookmarkButton::Remove(index));
+                    });
+            }
+        });
+    }
+}
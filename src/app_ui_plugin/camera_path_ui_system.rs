@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use super::app_plugin::camera_path_resource::CameraPathRecorder;
+use super::app_plugin::simulation_time_resource::SimulationTime;
+use super::components::{
+    CameraPathButton, CameraPathListTag, CameraPathLoadButton, CameraPathStatusTextTag,
+};
+use super::pan_cam::PanCam;
+use super::setup_system::spawn_button;
+
+pub fn update_camera_path_buttons(
+    button_query: Query<(&Interaction, &CameraPathButton), (Changed<Interaction>, With<Button>)>,
+    load_query: Query<(&Interaction, &CameraPathLoadButton), (Changed<Interaction>, With<Button>)>,
+    time: Res<Time>,
+    sim_time: Res<SimulationTime>,
+    mut recorder: ResMut<CameraPathRecorder>,
+    cam_query: Query<(&Transform, &OrthographicProjection), With<PanCam>>,
+) {
+    for (interaction, button) in &button_query {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        match button {
+            CameraPathButton::Keyframe => {
+                if let Ok((transform, projection)) = cam_query.get_single() {
+                    recorder.record(
+                        time.elapsed_seconds_f64(),
+                        transform.translation.truncate(),
+                        projection.scale,
+                        sim_time.years,
+                    );
+                }
+            }
+            CameraPathButton::Play => recorder.play(),
+            CameraPathButton::Stop => recorder.stop(),
+            CameraPathButton::Save => recorder.save(),
+            CameraPathButton::Refresh => recorder.refresh(),
+        }
+    }
+
+    for (interaction, load_button) in &load_query {
+        if *interaction == Interaction::Clicked {
+            recorder.load(&load_button.0);
+        }
+    }
+}
+
+// Show the recorder state and rebuild the saved paths list when it changes
+pub fn update_camera_path_list(
+    mut commands: Commands,
+    recorder: Res<CameraPathRecorder>,
+    asset_server: Res<AssetServer>,
+    list_query: Query<Entity, With<CameraPathListTag>>,
+    mut status_query: Query<&mut Text, With<CameraPathStatusTextTag>>,
+    mut shown_names: Local<Option<Vec<String>>>,
+) {
+    if !recorder.is_changed() {
+        return;
+    }
+
+    for mut text in &mut status_query {
+        if text.sections[0].value != recorder.status {
+            text.sections[0].value = recorder.status.clone();
+        }
+    }
+
+    // The recorder changes every frame while playing, the list rarely does
+    if shown_names.as_ref() == Some(&recorder.names) {
+        return;
+    }
+    *shown_names = Some(recorder.names.clone());
+
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    for list in &list_query {
+        commands.entity(list).despawn_descendants();
+        commands.entity(list).with_children(|parent| {
+            for name in &recorder.names {
+                spawn_button(parent, &font, name, CameraPathLoadButton(name.clone()));
+            }
+        });
+    }
+}
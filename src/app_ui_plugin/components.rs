@@ -1,9 +1,153 @@
 use bevy::prelude::*;
+use galaxy::export::ExportFormat;
+
+use super::setting_field::{SettingField, SettingsPage};
 
 #[derive(Component)]
 pub struct FpsTag;
 
 #[derive(Component)]
-pub struct StarUpButtonTag;
+pub struct EpochTextTag;
+
+#[derive(Component)]
+pub struct ConfigProblemsTextTag;
+
+#[derive(Component)]
+pub struct InspectorTag;
+
+#[derive(Component)]
+pub struct InspectorTextTag;
+
+#[derive(Component, Clone, Copy)]
+pub enum TimeButton {
+    Pause,
+    Reverse,
+    Slower,
+    Faster,
+    SeekStart,
+    SeekBackward,
+    SeekForward,
+    /// Type the epoch to jump to
+    SetEpoch,
+}
+
+/// Export the catalog of the objects at the current epoch
+#[derive(Component, Clone, Copy)]
+pub struct ExportButton(pub ExportFormat);
+
+#[derive(Component)]
+pub struct ExportStatusTextTag;
+
+#[derive(Component)]
+pub struct SettingsPanelTag;
+
+#[derive(Component)]
+pub struct SettingsBodyTag;
+
+#[derive(Component)]
+pub struct SettingsHideButtonTag;
+
+#[derive(Component, Clone, Copy)]
+pub struct SettingsPageButton(pub SettingsPage);
+
+/// Row of the settings panel, shown only on its page
+#[derive(Component)]
+pub struct SettingRow(pub SettingsPage);
+
+/// -/+ button of a field, `direction` is the number of steps
+#[derive(Component, Clone, Copy)]
+pub struct SettingStepButton {
+    pub field: SettingField,
+    pub direction: f64,
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct SettingToggleButton(pub SettingField);
+
+#[derive(Component, Clone, Copy)]
+pub struct SettingSlider(pub SettingField);
+
+#[derive(Component)]
+pub struct SettingSliderFill(pub SettingField);
+
+#[derive(Component)]
+pub struct SettingValueText(pub SettingField);
+
+/// Value of a field, click to type a new one
+#[derive(Component, Clone, Copy)]
+pub struct SettingEntryButton(pub SettingField);
+
+#[derive(Component, Clone, Copy)]
+pub enum PresetActionButton {
+    Save,
+    SaveAs,
+    Rename,
+    Refresh,
+}
+
+#[derive(Component, Clone)]
+pub struct PresetLoadButton(pub String);
+
+#[derive(Component)]
+pub struct PresetListTag;
+
+#[derive(Component)]
+pub struct PresetStatusTextTag;
+
+#[derive(Component, Clone, Copy)]
+pub enum SnapshotActionButton {
+    Save,
+    Refresh,
+}
+
+#[derive(Component, Clone)]
+pub struct SnapshotLoadButton(pub String);
+
+#[derive(Component)]
+pub struct SnapshotListTag;
+
+#[derive(Component)]
+pub struct SnapshotStatusTextTag;
+
+#[derive(Component, Clone, Copy)]
+pub enum BookmarkButton {
+    /// Keep the current view as a new bookmark
+    Add,
+    FlyTo(usize),
+    /// Type a new name for the bookmark
+    Rename(usize),
+    Remove(usize),
+}
+
+#[derive(Component)]
+pub struct BookmarkListTag;
+
+#[derive(Component, Clone, Copy)]
+pub enum CameraPathButton {
+    /// Record the current view, the first keyframe starts a new path
+    Keyframe,
+    Play,
+    Stop,
+    Save,
+    Refresh,
+}
+
+#[derive(Component, Clone)]
+pub struct CameraPathLoadButton(pub String);
+
+#[derive(Component)]
+pub struct CameraPathListTag;
+
+#[derive(Component)]
+pub struct CameraPathStatusTextTag;
+
+#[derive(Component, Clone, Copy)]
+pub enum FrameRecordButton {
+    /// Start or stop recording frames
+    Toggle,
+    /// Play the camera path from the start and record it to its end
+    CameraPath,
+}
+
 #[derive(Component)]
-pub struct StarDownButtonTag;
+pub struct FrameStatusTextTag;
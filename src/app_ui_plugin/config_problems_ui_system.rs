@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use super::app_plugin::config_problems_resource::ConfigProblems;
+use super::components::ConfigProblemsTextTag;
+
+pub fn update_config_problems_text(
+    config_problems: Res<ConfigProblems>,
+    mut query: Query<&mut Text, With<ConfigProblemsTextTag>>,
+) {
+    if !config_problems.is_changed() {
+        return;
+    }
+
+    let value = if config_problems.problems.is_empty() {
+        String::new()
+    } else {
+        let lines: Vec<String> = config_problems
+            .problems
+            .iter()
+            .map(|(source, problem)| format!("{}: {}", source, problem))
+            .collect();
+        format!("Settings problems:\n{}", lines.join("\n"))
+    };
+    for mut text in &mut query {
+        text.sections[0].value = value.clone();
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use super::app_plugin::catalog_export_resource::CatalogExport;
+use super::app_plugin::settings_resource::GalaxySettingsResource;
+use super::app_plugin::simulation_time_resource::SimulationTime;
+use super::app_plugin::star_component::{self, KindComponent, ObjectIndex, StarComponent};
+use super::components::{ExportButton, ExportStatusTextTag};
+
+pub fn update_export_buttons(
+    interaction_query: Query<(&Interaction, &ExportButton), (Changed<Interaction>, With<Button>)>,
+    stars: Query<(&StarComponent, &KindComponent, &ObjectIndex)>,
+    galaxy_settings: Res<GalaxySettingsResource>,
+    sim_time: Res<SimulationTime>,
+    mut catalog_export: ResMut<CatalogExport>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Clicked {
+            // Same order as the generator, so the files match `galaxy export`
+            let objects = star_component::galaxy_objects(stars.iter());
+            catalog_export.export(button.0, &objects, &galaxy_settings, sim_time.years);
+        }
+    }
+}
+
+pub fn update_export_status(
+    catalog_export: Res<CatalogExport>,
+    mut query: Query<&mut Text, With<ExportStatusTextTag>>,
+) {
+    if !catalog_export.is_changed() {
+        return;
+    }
+    for mut text in &mut query {
+        text.sections[0].value = catalog_export.status.clone();
+    }
+}
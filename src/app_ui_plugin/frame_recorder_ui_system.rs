@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use super::app_plugin::camera_path_resource::CameraPathRecorder;
+use super::app_plugin::frame_recorder_resource::FrameRecorder;
+use super::components::{FrameRecordButton, FrameStatusTextTag};
+
+pub fn update_frame_record_buttons(
+    interaction_query: Query<
+        (&Interaction, &FrameRecordButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut recorder: ResMut<FrameRecorder>,
+    mut camera_path: ResMut<CameraPathRecorder>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        match button {
+            FrameRecordButton::Toggle if recorder.recording.is_some() => recorder.stop(),
+            FrameRecordButton::Toggle => recorder.start(false),
+            FrameRecordButton::CameraPath => {
+                recorder.stop();
+                camera_path.play();
+                if camera_path.path.len() >= 2 {
+                    recorder.start(true);
+                }
+            }
+        }
+    }
+}
+
+pub fn update_frame_status(
+    recorder: Res<FrameRecorder>,
+    mut query: Query<&mut Text, With<FrameStatusTextTag>>,
+) {
+    if !recorder.is_changed() {
+        return;
+    }
+    for mut text in &mut query {
+        text.sections[0].value = recorder.status.clone();
+    }
+}
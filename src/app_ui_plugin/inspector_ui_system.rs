@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use galaxy::star;
+
+use super::app_plugin::selected_star_resource::SelectedStar;
+use super::app_plugin::star_component::{KindComponent, StarComponent};
+use super::components::{InspectorTag, InspectorTextTag};
+use super::pan_cam::PanCam;
+use super::time_ui_system::format_years;
+
+// Show the selected object, its position follows the simulated time
+pub fn update_inspector(
+    mut selected: ResMut<SelectedStar>,
+    star_query: Query<(&StarComponent, &KindComponent, &Transform)>,
+    cam_query: Query<&PanCam>,
+    mut panel_query: Query<&mut Style, With<InspectorTag>>,
+    mut text_query: Query<&mut Text, With<InspectorTextTag>>,
+) {
+    let object = selected
+        .entity
+        .and_then(|entity| star_query.get(entity).ok());
+    // Objects are despawned when the galaxy is regenerated
+    if object.is_none() && selected.entity.is_some() {
+        selected.entity = None;
+    }
+
+    let display = if object.is_some() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in &mut panel_query {
+        if style.display != display {
+            style.display = display;
+        }
+    }
+
+    let Some((star, kind, transform)) = object else {
+        return;
+    };
+    let position = transform.translation.truncate();
+    // The object's own angular speed, it differs from the rotation curve at its radius
+    let period = if star.vel_theta != 0. {
+        format_years(360. / star.vel_theta.abs() as f64)
+    } else {
+        "∞".to_string()
+    };
+
+    let follow = match cam_query.get_single() {
+        Ok(cam) if cam.follow == selected.entity && cam.co_rotate => "\nFollowing, co-rotating",
+        Ok(cam) if cam.follow == selected.entity => "\nFollowing",
+        _ => "",
+    };
+
+    for mut text in &mut text_query {
+        text.sections[0].value = format!(
+            "{}\n\
+             Temperature: {:.0} K, class {}\n\
+             Magnitude: {:.3}\n\
+             a: {:.1} pc, b: {:.1} pc\n\
+             theta0: {:.2} deg, tilt: {:.4} rad\n\
+             vel_theta: {:.3e} deg/yr\n\
+             Orbital period: {}\n\
+             Radius: {:.1} pc\n\
+             Position: ({:.1}, {:.1}) pc{}",
+            kind.name(),
+            star.temp,
+            star::spectral_class(star.temp),
+            star.mag,
+            star.a,
+            star.b,
+            star.theta0,
+            star.tilt_angle,
+            star.vel_theta,
+            period,
+            position.length(),
+            position.x,
+            position.y,
+            follow,
+        );
+    }
+}
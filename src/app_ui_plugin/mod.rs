@@ -1,18 +1,63 @@
-use bevy::prelude::{App, Plugin};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
 
 use super::app_plugin;
+use super::pan_cam;
+mod bookmark_ui_system;
+mod camera_path_ui_system;
 mod components;
+mod config_problems_ui_system;
+mod export_ui_system;
+mod frame_recorder_ui_system;
+mod inspector_ui_system;
+mod preset_ui_system;
+mod setting_field;
+mod settings_panel_resource;
 mod settings_ui_system;
 mod setup_system;
+mod snapshot_ui_system;
 mod statistics_system;
+mod text_entry_resource;
+mod text_entry_system;
+mod time_ui_system;
 
 pub struct AppUIPlugin;
 
 impl Plugin for AppUIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(setup_system::setup_ui)
+        app.init_resource::<settings_panel_resource::SettingsPanel>()
+            .init_resource::<text_entry_resource::TextEntry>()
+            .add_startup_system(setup_system::setup_ui)
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                text_entry_system::update_text_entry.after(InputSystem),
+            )
             .add_system(statistics_system::update_fps)
             .add_system(statistics_system::update_stars_count)
-            .add_system(settings_ui_system::update_stars_count);
+            .add_system(settings_ui_system::update_button_colors)
+            .add_system(settings_ui_system::update_setting_steps)
+            .add_system(settings_ui_system::update_setting_toggles)
+            .add_system(settings_ui_system::update_setting_sliders)
+            .add_system(settings_ui_system::update_setting_entries)
+            .add_system(settings_ui_system::update_setting_values)
+            .add_system(settings_ui_system::update_settings_page)
+            .add_system(settings_ui_system::update_settings_rows)
+            .add_system(settings_ui_system::block_camera_under_ui)
+            .add_system(preset_ui_system::update_preset_buttons)
+            .add_system(preset_ui_system::update_preset_list)
+            .add_system(bookmark_ui_system::update_bookmark_buttons)
+            .add_system(bookmark_ui_system::update_bookmark_list)
+            .add_system(camera_path_ui_system::update_camera_path_buttons)
+            .add_system(camera_path_ui_system::update_camera_path_list)
+            .add_system(frame_recorder_ui_system::update_frame_record_buttons)
+            .add_system(frame_recorder_ui_system::update_frame_status)
+            .add_system(snapshot_ui_system::update_snapshot_buttons)
+            .add_system(snapshot_ui_system::update_snapshot_list)
+            .add_system(time_ui_system::update_time_buttons)
+            .add_system(time_ui_system::update_epoch_text)
+            .add_system(export_ui_system::update_export_buttons)
+            .add_system(export_ui_system::update_export_status)
+            .add_system(inspector_ui_system::update_inspector)
+            .add_system(config_problems_ui_system::update_config_problems_text);
     }
 }
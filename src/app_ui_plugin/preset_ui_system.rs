@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use super::app_plugin::camera_bookmarks_resource::CameraBookmarks;
+use super::app_plugin::config_problems_resource::ConfigProblems;
+use super::app_plugin::preset_resource::PresetLibrary;
+use super::app_plugin::settings_resource::{DensityWaveResource, GalaxySettingsResource};
+use super::components::{PresetActionButton, PresetListTag, PresetLoadButton, PresetStatusTextTag};
+use super::setup_system::spawn_button;
+use super::text_entry_resource::{EntryTarget, TextEntry};
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_preset_buttons(
+    action_query: Query<(&Interaction, &PresetActionButton), (Changed<Interaction>, With<Button>)>,
+    load_query: Query<(&Interaction, &PresetLoadButton), (Changed<Interaction>, With<Button>)>,
+    keyboard: Res<Input<KeyCode>>,
+    mut library: ResMut<PresetLibrary>,
+    mut galaxy_settings: ResMut<GalaxySettingsResource>,
+    mut density_wave: ResMut<DensityWaveResource>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut config_problems: ResMut<ConfigProblems>,
+    mut entry: ResMut<TextEntry>,
+) {
+    for (interaction, action) in &action_query {
+        if *interaction == Interaction::Clicked {
+            match action {
+                PresetActionButton::Save => {
+                    library.save(&galaxy_settings, &density_wave, &bookmarks.bookmarks)
+                }
+                PresetActionButton::SaveAs => entry.start(EntryTarget::PresetName),
+                PresetActionButton::Rename => entry.start(EntryTarget::PresetRename),
+                PresetActionButton::Refresh => library.refresh(),
+            }
+        }
+    }
+
+    // The typed name is used once Enter is pressed
+    if let Some((target @ (EntryTarget::PresetName | EntryTarget::PresetRename), _)) =
+        entry.submitted
+    {
+        let text = entry
+            .submitted
+            .take()
+            .map(|(_, text)| text)
+            .unwrap_or_default();
+        match (target, galaxy::preset::clean_name(&text)) {
+            (EntryTarget::PresetName, Some(name)) => {
+                library.save_as(&name, &galaxy_settings, &density_wave, &bookmarks.bookmarks)
+            }
+            (_, Some(name)) => library.rename(&name),
+            (_, None) => library.status = "A preset needs a name".to_string(),
+        }
+    }
+
+    // Ctrl+S saves the current preset
+    let is_control = keyboard.pressed(KeyCode::LControl) || keyboard.pressed(KeyCode::RControl);
+    if is_control && keyboard.just_pressed(KeyCode::S) {
+        library.save(&galaxy_settings, &density_wave, &bookmarks.bookmarks);
+    }
+
+    for (interaction, load_button) in &load_query {
+        if *interaction == Interaction::Clicked {
+            if let Some((preset, problems)) = library.load(&load_button.0) {
+                **galaxy_settings = preset.galaxy_settings;
+                **density_wave = preset.density_wave;
+                bookmarks.bookmarks = preset.bookmarks;
+                // The preset replaces all settings, older problems no longer apply
+                config_problems.clear();
+                config_problems.report(&format!("preset {}", load_button.0), problems);
+            }
+        }
+    }
+}
+
+// Rebuild the preset browser when the library changes
+pub fn update_preset_list(
+    mut commands: Commands,
+    library: Res<PresetLibrary>,
+    asset_server: Res<AssetServer>,
+    list_query: Query<Entity, With<PresetListTag>>,
+    mut status_query: Query<&mut Text, With<PresetStatusTextTag>>,
+    entry: Res<TextEntry>,
+) {
+    if entry.is_changed() {
+        // The status line doubles as the name field
+        for mut text in &mut status_query {
+            text.sections[0].value = if entry.is_editing(EntryTarget::PresetName) {
+                format!("Save as: {}", entry.display())
+            } else if entry.is_editing(EntryTarget::PresetRename) {
+                format!("Rename to: {}", entry.display())
+            } else {
+                status_text(&library)
+            };
+        }
+    }
+
+    if !library.is_changed() {
+        return;
+    }
+
+    for mut text in &mut status_query {
+        text.sections[0].value = status_text(&library);
+    }
+
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    for list in &list_query {
+        commands.entity(list).despawn_descendants();
+        commands.entity(list).with_children(|parent| {
+            for name in &library.names {
+                spawn_button(parent, &font, name, PresetLoadButton(name.clone()));
+            }
+        });
+    }
+}
+
+fn status_text(library: &PresetLibrary) -> String {
+    match &library.current {
+        Some(current) => format!("Current: {}\n{}", current, library.status),
+        None => library.status.clone(),
+    }
+}
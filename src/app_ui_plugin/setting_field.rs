@@ -0,0 +1,401 @@
+use super::app_plugin::density_wave::DensityWave;
+use super::app_plugin::galaxy_setting_component::{ArmModel, GalaxySettings, Morphology};
+
+/// Page of the settings panel
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum SettingsPage {
+    #[default]
+    Galaxy,
+    Structure,
+    Objects,
+    DensityWave,
+    Presets,
+}
+
+impl SettingsPage {
+    pub const ALL: [SettingsPage; 5] = [
+        SettingsPage::Galaxy,
+        SettingsPage::Structure,
+        SettingsPage::Objects,
+        SettingsPage::DensityWave,
+        SettingsPage::Presets,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsPage::Galaxy => "Galaxy",
+            SettingsPage::Structure => "Structure",
+            SettingsPage::Objects => "Objects",
+            SettingsPage::DensityWave => "Density wave",
+            SettingsPage::Presets => "Presets",
+        }
+    }
+}
+
+/// How a field is edited in the settings panel
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FieldWidget {
+    /// Slider with -/+ steppers
+    Slider,
+    /// Only -/+ steppers, for values without a meaningful range
+    Stepper,
+    /// Only typed in, for values `f64` can't hold
+    Entry,
+    Toggle,
+}
+
+/// Every editable field of `GalaxySettings` and `DensityWave`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettingField {
+    Radius,
+    BulgeRadius,
+    FarFieldRadius,
+    AngularOffset,
+    InnerExcentricity,
+    OutterExcentricity,
+    EllipseDisturbances,
+    EllipseDisturbancesDamping,
+    HasDarkMatter,
+    PertN,
+    PertAmp,
+    Seed,
+    BarLength,
+    BarAxisRatio,
+    BarPatternSpeed,
+    BarStarFraction,
+    Morphology,
+    EllipticalClass,
+    SersicIndex,
+    ArmModel,
+    ArmCount,
+    ArmPitch,
+    ArmWidth,
+    CountStars,
+    CountDusts,
+    CountDustsFilaments,
+    CountH2,
+    CountH2Core,
+    BaseTemp,
+    DustRenderSize,
+    WaveMin,
+    WaveMax,
+    WaveSteps,
+    WaveI0,
+    WaveK,
+    WaveA,
+    WaveBulgeRadius,
+}
+
+impl SettingField {
+    pub const ALL: [SettingField; 37] = [
+        SettingField::Radius,
+        SettingField::BulgeRadius,
+        SettingField::FarFieldRadius,
+        SettingField::AngularOffset,
+        SettingField::InnerExcentricity,
+        SettingField::OutterExcentricity,
+        SettingField::EllipseDisturbances,
+        SettingField::EllipseDisturbancesDamping,
+        SettingField::HasDarkMatter,
+        SettingField::PertN,
+        SettingField::PertAmp,
+        SettingField::Seed,
+        SettingField::BarLength,
+        SettingField::BarAxisRatio,
+        SettingField::BarPatternSpeed,
+        SettingField::BarStarFraction,
+        SettingField::Morphology,
+        SettingField::EllipticalClass,
+        SettingField::SersicIndex,
+        SettingField::ArmModel,
+        SettingField::ArmCount,
+        SettingField::ArmPitch,
+        SettingField::ArmWidth,
+        SettingField::CountStars,
+        SettingField::CountDusts,
+        SettingField::CountDustsFilaments,
+        SettingField::CountH2,
+        SettingField::CountH2Core,
+        SettingField::BaseTemp,
+        SettingField::DustRenderSize,
+        SettingField::WaveMin,
+        SettingField::WaveMax,
+        SettingField::WaveSteps,
+        SettingField::WaveI0,
+        SettingField::WaveK,
+        SettingField::WaveA,
+        SettingField::WaveBulgeRadius,
+    ];
+
+    pub fn page(&self) -> SettingsPage {
+        match self {
+            SettingField::CountStars
+            | SettingField::CountDusts
+            | SettingField::CountDustsFilaments
+            | SettingField::CountH2
+            | SettingField::CountH2Core
+            | SettingField::BaseTemp
+            | SettingField::DustRenderSize => SettingsPage::Objects,
+            SettingField::BarLength
+            | SettingField::BarAxisRatio
+            | SettingField::BarPatternSpeed
+            | SettingField::BarStarFraction
+            | SettingField::Morphology
+            | SettingField::EllipticalClass
+            | SettingField::SersicIndex
+            | SettingField::ArmModel
+            | SettingField::ArmCount
+            | SettingField::ArmPitch
+            | SettingField::ArmWidth => SettingsPage::Structure,
+            SettingField::WaveMin
+            | SettingField::WaveMax
+            | SettingField::WaveSteps
+            | SettingField::WaveI0
+            | SettingField::WaveK
+            | SettingField::WaveA
+            | SettingField::WaveBulgeRadius => SettingsPage::DensityWave,
+            _ => SettingsPage::Galaxy,
+        }
+    }
+
+    pub fn is_density_wave(&self) -> bool {
+        self.page() == SettingsPage::DensityWave
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingField::Radius => "Radius",
+            SettingField::BulgeRadius => "Bulge radius",
+            SettingField::FarFieldRadius => "Far field radius",
+            SettingField::AngularOffset => "Angular offset",
+            SettingField::InnerExcentricity => "Inner excentricity",
+            SettingField::OutterExcentricity => "Outer excentricity",
+            SettingField::EllipseDisturbances => "Disturbances",
+            SettingField::EllipseDisturbancesDamping => "Disturb. damping",
+            SettingField::HasDarkMatter => "Dark matter",
+            SettingField::PertN => "Perturbation n",
+            SettingField::PertAmp => "Perturbation amp",
+            SettingField::Seed => "Seed",
+            SettingField::BarLength => "Bar length",
+            SettingField::BarAxisRatio => "Bar axis ratio",
+            SettingField::BarPatternSpeed => "Bar pattern speed",
+            SettingField::BarStarFraction => "Bar stars",
+            SettingField::Morphology => "Morphology",
+            SettingField::EllipticalClass => "Elliptical class",
+            SettingField::SersicIndex => "Sersic index",
+            SettingField::ArmModel => "Arm model",
+            SettingField::ArmCount => "Arms",
+            SettingField::ArmPitch => "Arm pitch",
+            SettingField::ArmWidth => "Arm width",
+            SettingField::CountStars => "Stars",
+            SettingField::CountDusts => "Dust",
+            SettingField::CountDustsFilaments => "Dust filaments",
+            SettingField::CountH2 => "H2 regions",
+            SettingField::CountH2Core => "H2 cores",
+            SettingField::BaseTemp => "Base temperature",
+            SettingField::DustRenderSize => "Dust render size",
+            SettingField::WaveMin => "Min radius",
+            SettingField::WaveMax => "Max radius",
+            SettingField::WaveSteps => "Steps",
+            SettingField::WaveI0 => "Central intensity",
+            SettingField::WaveK => "Bulge k",
+            SettingField::WaveA => "Disc scale length",
+            SettingField::WaveBulgeRadius => "Bulge radius",
+        }
+    }
+
+    pub fn widget(&self) -> FieldWidget {
+        match self {
+            SettingField::HasDarkMatter => FieldWidget::Toggle,
+            SettingField::Seed => FieldWidget::Entry,
+            SettingField::Morphology | SettingField::ArmModel => FieldWidget::Stepper,
+            _ => FieldWidget::Slider,
+        }
+    }
+
+    /// Value can be typed in
+    pub fn has_text_entry(&self) -> bool {
+        matches!(self.widget(), FieldWidget::Slider | FieldWidget::Entry)
+    }
+
+    /// Minimum, maximum and step of the field
+    pub fn range(&self) -> (f64, f64, f64) {
+        match self {
+            SettingField::Radius => (1000., 40000., 500.),
+            SettingField::BulgeRadius => (0., 20000., 100.),
+            SettingField::FarFieldRadius => (1000., 80000., 1000.),
+            SettingField::AngularOffset => (0., 0.002, 0.00001),
+            SettingField::InnerExcentricity => (0.5, 1.5, 0.01),
+            SettingField::OutterExcentricity => (0.5, 1.5, 0.01),
+            SettingField::EllipseDisturbances => (0., 10., 1.),
+            SettingField::EllipseDisturbancesDamping => (2., 100., 1.),
+            SettingField::HasDarkMatter => (0., 1., 1.),
+            SettingField::PertN => (0., 10., 1.),
+            SettingField::PertAmp => (0., 200., 1.),
+            SettingField::Seed => (0., u64::MAX as f64, 1.),
+            SettingField::BarLength => (0., 20000., 100.),
+            SettingField::BarAxisRatio => (0.05, 1., 0.01),
+            SettingField::BarPatternSpeed => (-100., 100., 1.),
+            SettingField::BarStarFraction => (0., 1., 0.01),
+            SettingField::Morphology => (0., (Morphology::ALL.len() - 1) as f64, 1.),
+            SettingField::EllipticalClass => (0., 7., 1.),
+            SettingField::SersicIndex => (0.5, 10., 0.1),
+            SettingField::ArmModel => (0., (ArmModel::ALL.len() - 1) as f64, 1.),
+            SettingField::ArmCount => (2., 6., 1.),
+            SettingField::ArmPitch => (1., 60., 0.5),
+            SettingField::ArmWidth => (0., 3000., 50.),
+            SettingField::CountStars => (1000., 200000., 1000.),
+            SettingField::CountDusts => (0., 200000., 1000.),
+            SettingField::CountDustsFilaments => (0., 200000., 1000.),
+            SettingField::CountH2 => (0., 5000., 100.),
+            SettingField::CountH2Core => (0., 5000., 100.),
+            SettingField::BaseTemp => (1000., 10000., 100.),
+            SettingField::DustRenderSize => (1., 300., 1.),
+            SettingField::WaveMin => (0., 10000., 100.),
+            SettingField::WaveMax => (1000., 100000., 1000.),
+            SettingField::WaveSteps => (100., 10000., 100.),
+            SettingField::WaveI0 => (0.1, 10., 0.1),
+            SettingField::WaveK => (0.001, 0.1, 0.001),
+            SettingField::WaveA => (100., 20000., 100.),
+            SettingField::WaveBulgeRadius => (0., 20000., 100.),
+        }
+    }
+
+    pub fn get(&self, galaxy: &GalaxySettings, wave: &DensityWave) -> f64 {
+        match self {
+            SettingField::Radius => galaxy.radius as f64,
+            SettingField::BulgeRadius => galaxy.bulge_radius as f64,
+            SettingField::FarFieldRadius => galaxy.far_field_radius as f64,
+            SettingField::AngularOffset => galaxy.angular_offset as f64,
+            SettingField::InnerExcentricity => galaxy.inner_excentricity as f64,
+            SettingField::OutterExcentricity => galaxy.outter_excentricity as f64,
+            SettingField::EllipseDisturbances => galaxy.ellipse_disturbances as f64,
+            SettingField::EllipseDisturbancesDamping => galaxy.ellipse_disturbances_damping as f64,
+            SettingField::HasDarkMatter => galaxy.has_dark_matter as u8 as f64,
+            SettingField::PertN => galaxy.pert_n as f64,
+            SettingField::PertAmp => galaxy.pert_amp as f64,
+            SettingField::Seed => galaxy.seed as f64,
+            SettingField::BarLength => galaxy.bar_length as f64,
+            SettingField::BarAxisRatio => galaxy.bar_axis_ratio as f64,
+            SettingField::BarPatternSpeed => galaxy.bar_pattern_speed as f64,
+            SettingField::BarStarFraction => galaxy.bar_star_fraction as f64,
+            SettingField::Morphology => Morphology::ALL
+                .iter()
+                .position(|morphology| *morphology == galaxy.morphology)
+                .unwrap_or_default() as f64,
+            SettingField::EllipticalClass => galaxy.elliptical_class as f64,
+            SettingField::SersicIndex => galaxy.sersic_index as f64,
+            SettingField::ArmModel => ArmModel::ALL
+                .iter()
+                .position(|model| *model == galaxy.arm_model)
+                .unwrap_or_default() as f64,
+            SettingField::ArmCount => galaxy.arm_count as f64,
+            SettingField::ArmPitch => galaxy.arm_pitch as f64,
+            SettingField::ArmWidth => galaxy.arm_width as f64,
+            SettingField::CountStars => galaxy.count_stars as f64,
+            SettingField::CountDusts => galaxy.count_dusts as f64,
+            SettingField::CountDustsFilaments => galaxy.count_dusts_filaments as f64,
+            SettingField::CountH2 => galaxy.count_h2 as f64,
+            SettingField::CountH2Core => galaxy.count_h2_core as f64,
+            SettingField::BaseTemp => galaxy.base_temp as f64,
+            SettingField::DustRenderSize => galaxy.dust_render_size as f64,
+            SettingField::WaveMin => wave.min as f64,
+            SettingField::WaveMax => wave.max as f64,
+            SettingField::WaveSteps => wave.steps as f64,
+            SettingField::WaveI0 => wave.i0 as f64,
+            SettingField::WaveK => wave.k as f64,
+            SettingField::WaveA => wave.a as f64,
+            SettingField::WaveBulgeRadius => wave.bulge_radius as f64,
+        }
+    }
+
+    /// Write a value into `GalaxySettings`. Density wave fields and the seed,
+    /// which is only set as the integer it is, are ignored.
+    pub fn set_galaxy(&self, galaxy: &mut GalaxySettings, value: f64) {
+        let value = self.clamp(value);
+        match self {
+            SettingField::Radius => galaxy.radius = value as f32,
+            SettingField::BulgeRadius => galaxy.bulge_radius = value as f32,
+            SettingField::FarFieldRadius => galaxy.far_field_radius = value as f32,
+            SettingField::AngularOffset => galaxy.angular_offset = value as f32,
+            SettingField::InnerExcentricity => galaxy.inner_excentricity = value as f32,
+            SettingField::OutterExcentricity => galaxy.outter_excentricity = value as f32,
+            SettingField::EllipseDisturbances => galaxy.ellipse_disturbances = value as i32,
+            SettingField::EllipseDisturbancesDamping => {
+                galaxy.ellipse_disturbances_damping = value as i32
+            }
+            SettingField::HasDarkMatter => galaxy.has_dark_matter = value >= 0.5,
+            SettingField::PertN => galaxy.pert_n = value as i32,
+            SettingField::PertAmp => galaxy.pert_amp = value as i32,
+            SettingField::BarLength => galaxy.bar_length = value as f32,
+            SettingField::BarAxisRatio => galaxy.bar_axis_ratio = value as f32,
+            SettingField::BarPatternSpeed => galaxy.bar_pattern_speed = value as f32,
+            SettingField::BarStarFraction => galaxy.bar_star_fraction = value as f32,
+            SettingField::Morphology => galaxy.morphology = Morphology::ALL[value as usize],
+            SettingField::EllipticalClass => galaxy.elliptical_class = value as u8,
+            SettingField::SersicIndex => galaxy.sersic_index = value as f32,
+            SettingField::ArmModel => galaxy.arm_model = ArmModel::ALL[value as usize],
+            SettingField::ArmCount => galaxy.arm_count = value as u8,
+            SettingField::ArmPitch => galaxy.arm_pitch = value as f32,
+            SettingField::ArmWidth => galaxy.arm_width = value as f32,
+            SettingField::CountStars => galaxy.count_stars = value as usize,
+            SettingField::CountDusts => galaxy.count_dusts = value as usize,
+            SettingField::CountDustsFilaments => galaxy.count_dusts_filaments = value as usize,
+            SettingField::CountH2 => galaxy.count_h2 = value as usize,
+            SettingField::CountH2Core => galaxy.count_h2_core = value as usize,
+            SettingField::BaseTemp => galaxy.base_temp = value as f32,
+            SettingField::DustRenderSize => galaxy.dust_render_size = value as f32,
+            _ => {}
+        }
+    }
+
+    /// Write a value into `DensityWave`. The wave has to be built afterwards.
+    pub fn set_wave(&self, wave: &mut DensityWave, value: f64) {
+        let value = self.clamp(value);
+        match self {
+            SettingField::WaveMin => wave.min = value as f32,
+            SettingField::WaveMax => wave.max = value as f32,
+            // Simpson integration needs an even number of steps
+            SettingField::WaveSteps => wave.steps = (value as i32 / 2) * 2,
+            SettingField::WaveI0 => wave.i0 = value as f32,
+            SettingField::WaveK => wave.k = value as f32,
+            SettingField::WaveA => wave.a = value as f32,
+            SettingField::WaveBulgeRadius => wave.bulge_radius = value as f32,
+            _ => {}
+        }
+    }
+
+    /// Snap to the step and keep inside the range
+    pub fn clamp(&self, value: f64) -> f64 {
+        let (min, max, step) = self.range();
+        (((value - min) / step).round() * step + min).clamp(min, max)
+    }
+
+    /// Current value as shown in the panel. The seed is shown as the integer it is,
+    /// not through `f64` which can't hold all of them.
+    pub fn display(&self, galaxy: &GalaxySettings, wave: &DensityWave) -> String {
+        match self {
+            SettingField::Seed => galaxy.seed.to_string(),
+            _ => self.format(self.get(galaxy, wave)),
+        }
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        let (_, _, step) = self.range();
+        match self {
+            SettingField::Morphology => return Morphology::ALL[value as usize].name().to_string(),
+            SettingField::EllipticalClass => return format!("E{:.0}", value),
+            SettingField::ArmModel => return ArmModel::ALL[value as usize].name().to_string(),
+            _ => {}
+        }
+        match self.widget() {
+            FieldWidget::Toggle if value >= 0.5 => "on".to_string(),
+            FieldWidget::Toggle => "off".to_string(),
+            _ if step >= 1. => format!("{:.0}", value),
+            _ => {
+                let decimals = (-step.log10()).ceil() as usize;
+                format!("{:.*}", decimals, value)
+            }
+        }
+    }
+}
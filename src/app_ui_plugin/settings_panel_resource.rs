@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use super::setting_field::{SettingField, SettingsPage};
+
+#[derive(Resource)]
+pub struct SettingsPanel {
+    pub page: SettingsPage,
+    pub is_visible: bool,
+    /// Slider held down and its value, applied when it is let go
+    pub drag: Option<(SettingField, f64)>,
+}
+
+impl Default for SettingsPanel {
+    fn default() -> Self {
+        Self {
+            page: SettingsPage::default(),
+            is_visible: true,
+            drag: None,
+        }
+    }
+}
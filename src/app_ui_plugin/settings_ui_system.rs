@@ -1,33 +1,31 @@
 use bevy::prelude::*;
+use galaxy::validation;
 
-use super::app_plugin::galaxy_setting_component::GalaxySettings;
-use super::components::StarDownButtonTag;
-use super::components::StarUpButtonTag;
+use super::app_plugin::config_problems_resource::ConfigProblems;
+use super::app_plugin::settings_resource::{DensityWaveResource, GalaxySettingsResource};
+use super::components::{
+    SettingEntryButton, SettingRow, SettingSlider, SettingSliderFill, SettingStepButton,
+    SettingToggleButton, SettingValueText, SettingsBodyTag, SettingsHideButtonTag,
+    SettingsPageButton,
+};
+use super::pan_cam::PanCam;
+use super::setting_field::SettingField;
+use super::settings_panel_resource::SettingsPanel;
+use super::text_entry_resource::{EntryTarget, TextEntry};
 
-const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
-const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+pub const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+pub const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
 
-pub fn update_stars_count(
+pub fn update_button_colors(
     mut interaction_query: Query<
-        (
-            &Interaction,
-            &mut BackgroundColor,
-            AnyOf<(With<StarDownButtonTag>, With<StarUpButtonTag>)>,
-        ),
+        (&Interaction, &mut BackgroundColor),
         (Changed<Interaction>, With<Button>),
     >,
-    mut galaxy_settings: ResMut<GalaxySettings>,
 ) {
-    for (interaction, mut color, down_or_up) in &mut interaction_query {
+    for (interaction, mut color) in &mut interaction_query {
         match *interaction {
             Interaction::Clicked => {
-                if down_or_up.0.is_some() {
-                    galaxy_settings.count_stars = (galaxy_settings.count_stars - 1000).max(0);
-                }
-                if down_or_up.1.is_some() {
-                    galaxy_settings.count_stars += 1000;
-                }
                 *color = PRESSED_BUTTON.into();
             }
             Interaction::Hovered => {
@@ -39,3 +37,266 @@ pub fn update_stars_count(
         }
     }
 }
+
+pub fn update_setting_steps(
+    interaction_query: Query<
+        (&Interaction, &SettingStepButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut galaxy_settings: ResMut<GalaxySettingsResource>,
+    mut density_wave: ResMut<DensityWaveResource>,
+    mut config_problems: ResMut<ConfigProblems>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Clicked {
+            let (_, _, step) = button.field.range();
+            let value = button.field.get(&galaxy_settings, &density_wave) + button.direction * step;
+            apply_value(
+                button.field,
+                value,
+                &mut galaxy_settings,
+                &mut density_wave,
+                &mut config_problems,
+            );
+        }
+    }
+}
+
+pub fn update_setting_toggles(
+    interaction_query: Query<
+        (&Interaction, &SettingToggleButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut galaxy_settings: ResMut<GalaxySettingsResource>,
+    mut density_wave: ResMut<DensityWaveResource>,
+    mut config_problems: ResMut<ConfigProblems>,
+) {
+    for (interaction, toggle) in &interaction_query {
+        if *interaction == Interaction::Clicked {
+            let value = 1. - toggle.0.get(&galaxy_settings, &density_wave);
+            apply_value(
+                toggle.0,
+                value,
+                &mut galaxy_settings,
+                &mut density_wave,
+                &mut config_problems,
+            );
+        }
+    }
+}
+
+// Sliders follow the cursor for as long as they are held,
+// the galaxy is generated again once they are let go
+pub fn update_setting_sliders(
+    windows: Res<Windows>,
+    slider_query: Query<(&Interaction, &Node, &GlobalTransform, &SettingSlider)>,
+    mut panel: ResMut<SettingsPanel>,
+    mut galaxy_settings: ResMut<GalaxySettingsResource>,
+    mut density_wave: ResMut<DensityWaveResource>,
+    mut config_problems: ResMut<ConfigProblems>,
+) {
+    let cursor_position = windows.get_primary().and_then(|w| w.cursor_position());
+
+    for (interaction, node, transform, slider) in &slider_query {
+        if *interaction != Interaction::Clicked {
+            if let Some((field, value)) = panel.drag.filter(|(field, _)| *field == slider.0) {
+                panel.drag = None;
+                apply_value(
+                    field,
+                    value,
+                    &mut galaxy_settings,
+                    &mut density_wave,
+                    &mut config_problems,
+                );
+            }
+            continue;
+        }
+        let width = node.size().x;
+        let Some(cursor_position) = cursor_position.filter(|_| width > 0.) else {
+            continue;
+        };
+        let left = transform.translation().x - width / 2.;
+        let t = ((cursor_position.x - left) / width).clamp(0., 1.) as f64;
+        let (min, max, _) = slider.0.range();
+        let drag = Some((slider.0, slider.0.clamp(min + t * (max - min))));
+        if panel.drag != drag {
+            panel.drag = drag;
+        }
+    }
+}
+
+pub fn update_setting_entries(
+    interaction_query: Query<
+        (&Interaction, &SettingEntryButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut entry: ResMut<TextEntry>,
+    mut galaxy_settings: ResMut<GalaxySettingsResource>,
+    mut density_wave: ResMut<DensityWaveResource>,
+    mut config_problems: ResMut<ConfigProblems>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Clicked {
+            entry.start(EntryTarget::Setting(button.0));
+        }
+    }
+
+    let Some((EntryTarget::Setting(field), _)) = entry.submitted else {
+        return;
+    };
+    let Some((_, text)) = entry.submitted.take() else {
+        return;
+    };
+    // Values that don't parse are dropped, the field keeps its value
+    if field == SettingField::Seed {
+        if let Ok(seed) = text.trim().parse::<u64>() {
+            if galaxy_settings.seed != seed {
+                galaxy_settings.seed = seed;
+            }
+        }
+    } else if let Ok(value) = text.trim().parse::<f64>() {
+        apply_value(
+            field,
+            value,
+            &mut galaxy_settings,
+            &mut density_wave,
+            &mut config_problems,
+        );
+    }
+}
+
+fn apply_value(
+    field: SettingField,
+    value: f64,
+    galaxy_settings: &mut ResMut<GalaxySettingsResource>,
+    density_wave: &mut ResMut<DensityWaveResource>,
+    config_problems: &mut ResMut<ConfigProblems>,
+) {
+    // Don't trigger a regeneration if nothing changed
+    if field.clamp(value) == field.get(galaxy_settings, density_wave) {
+        return;
+    }
+
+    if field.is_density_wave() {
+        field.set_wave(density_wave, value);
+    } else {
+        field.set_galaxy(galaxy_settings, value);
+    }
+
+    // A value can break another field, like a radius below the bulge radius.
+    // That field is reset as in a loaded file and the problem shown.
+    let mut problems = validation::validate_galaxy_settings(galaxy_settings);
+    let mut wave = density_wave.0.clone();
+    problems.extend(
+        validation::validate_density_wave(&mut wave, galaxy_settings)
+            .into_iter()
+            .map(|problem| problem.in_section("density_wave")),
+    );
+    // A changed wave regenerates every object, not only the added ones
+    if density_wave.0 != wave {
+        density_wave.0 = wave;
+    }
+    config_problems.report("settings panel", problems);
+}
+
+pub fn update_setting_values(
+    galaxy_settings: Res<GalaxySettingsResource>,
+    density_wave: Res<DensityWaveResource>,
+    panel: Res<SettingsPanel>,
+    entry: Res<TextEntry>,
+    mut text_query: Query<(&mut Text, &SettingValueText)>,
+    mut fill_query: Query<(&mut Style, &SettingSliderFill)>,
+) {
+    if !galaxy_settings.is_changed()
+        && !density_wave.is_changed()
+        && !panel.is_changed()
+        && !entry.is_changed()
+    {
+        return;
+    }
+
+    // A held slider shows the value it will apply
+    let value_of = |field: SettingField| match panel.drag {
+        Some((dragged, value)) if dragged == field => value,
+        _ => field.get(&galaxy_settings, &density_wave),
+    };
+
+    for (mut text, value_text) in &mut text_query {
+        let field = value_text.0;
+        text.sections[0].value = if entry.is_editing(EntryTarget::Setting(field)) {
+            entry.display()
+        } else if matches!(panel.drag, Some((dragged, _)) if dragged == field) {
+            field.format(value_of(field))
+        } else {
+            field.display(&galaxy_settings, &density_wave)
+        };
+    }
+
+    for (mut style, fill) in &mut fill_query {
+        let (min, max, _) = fill.0.range();
+        let value = value_of(fill.0);
+        let t = ((value - min) / (max - min)).clamp(0., 1.) as f32;
+        style.size.width = Val::Percent(t * 100.);
+    }
+}
+
+pub fn update_settings_page(
+    page_query: Query<(&Interaction, &SettingsPageButton), (Changed<Interaction>, With<Button>)>,
+    hide_query: Query<&Interaction, (Changed<Interaction>, With<SettingsHideButtonTag>)>,
+    mut panel: ResMut<SettingsPanel>,
+) {
+    for (interaction, page_button) in &page_query {
+        if *interaction == Interaction::Clicked {
+            panel.page = page_button.0;
+            panel.is_visible = true;
+        }
+    }
+
+    for interaction in &hide_query {
+        if *interaction == Interaction::Clicked {
+            panel.is_visible = !panel.is_visible;
+        }
+    }
+}
+
+pub fn update_settings_rows(
+    panel: Res<SettingsPanel>,
+    mut body_query: Query<&mut Style, (With<SettingsBodyTag>, Without<SettingRow>)>,
+    mut row_query: Query<(&mut Style, &SettingRow)>,
+) {
+    if !panel.is_changed() {
+        return;
+    }
+
+    for mut style in &mut body_query {
+        style.display = if panel.is_visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+
+    for (mut style, row) in &mut row_query {
+        style.display = if row.0 == panel.page {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+// Don't pan or zoom the galaxy while the cursor is on the UI
+pub fn block_camera_under_ui(
+    interaction_query: Query<&Interaction>,
+    mut pan_cam_query: Query<&mut PanCam>,
+) {
+    let is_ui_used = interaction_query
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+
+    for mut pan_cam in &mut pan_cam_query {
+        if pan_cam.enabled == is_ui_used {
+            pan_cam.enabled = !is_ui_used;
+        }
+    }
+}
@@ -1,15 +1,47 @@
 use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use galaxy::export::ExportFormat;
 
+use super::components::BookmarkButton;
+use super::components::BookmarkListTag;
+use super::components::CameraPathButton;
+use super::components::CameraPathListTag;
+use super::components::CameraPathStatusTextTag;
+use super::components::ConfigProblemsTextTag;
+use super::components::EpochTextTag;
+use super::components::ExportButton;
+use super::components::ExportStatusTextTag;
 use super::components::FpsTag;
-use super::components::StarDownButtonTag;
-use super::components::StarUpButtonTag;
+use super::components::FrameRecordButton;
+use super::components::FrameStatusTextTag;
+use super::components::InspectorTag;
+use super::components::InspectorTextTag;
+use super::components::PresetActionButton;
+use super::components::PresetListTag;
+use super::components::PresetStatusTextTag;
+use super::components::SettingEntryButton;
+use super::components::SettingRow;
+use super::components::SettingSlider;
+use super::components::SettingSliderFill;
+use super::components::SettingStepButton;
+use super::components::SettingToggleButton;
+use super::components::SettingValueText;
+use super::components::SettingsBodyTag;
+use super::components::SettingsHideButtonTag;
+use super::components::SettingsPageButton;
+use super::components::SettingsPanelTag;
+use super::components::SnapshotActionButton;
+use super::components::SnapshotListTag;
+use super::components::SnapshotStatusTextTag;
+use super::components::TimeButton;
+use super::setting_field::{FieldWidget, SettingField, SettingsPage};
 
 pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
     let font = asset_server.load("fonts/FiraMono-Medium.ttf");
     commands.spawn((
         TextBundle::from_sections([
             TextSection::new(
-                "Move by mouse draging. Zoome by scrolling\n",
+                "Move by mouse draging, WASD or arrows. Zoome by scrolling or +/-\nSpace: pause, [ ]: speed, R: reverse, Home: epoch 0\nClick: inspect an object, F: follow it, C: co-rotate, Esc: close\n1-9: fly to a bookmark, Shift+1-9: bookmark the view\nK: record a camera keyframe, P: play the camera path, F9: record frames (offline renderer)\n",
                 TextStyle {
                     font: font.clone(),
                     font_size: 18.0,
@@ -45,48 +77,584 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
         FpsTag,
     ));
 
-    // Buttons
+    spawn_settings_panel(&mut commands, &font);
+
+    // Problems found in the settings files
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: font.clone(),
+                font_size: 14.0,
+                color: Color::rgb(1.0, 0.4, 0.3),
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                ..default()
+            },
+            max_size: Size::new(Val::Px(600.0), Val::Undefined),
+            ..default()
+        }),
+        ConfigProblemsTextTag,
+    ));
+
+    // Selected object, hidden while nothing is selected
     commands
         .spawn((
-            ButtonBundle {
+            NodeBundle {
                 style: Style {
-                    size: Size::new(Val::Px(100.0), Val::Px(40.0)),
+                    position_type: PositionType::Absolute,
                     position: UiRect {
-                        top: Val::Px(10.0),
-                        left: Val::Px(10.0),
+                        bottom: Val::Px(80.0),
+                        right: Val::Px(10.0),
                         ..default()
                     },
-                    // horizontally center child text
-                    justify_content: JustifyContent::Center,
-                    // vertically center child text
-                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(6.0)),
+                    display: Display::None,
                     ..default()
                 },
-                background_color: Color::rgba(1., 1., 1., 0.3).into(),
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
                 ..default()
             },
-            StarUpButtonTag,
+            InspectorTag,
         ))
         .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                "+ 1000 stars",
-                TextStyle {
-                    font: font.clone(),
-                    font_size: 13.0,
-                    color: Color::WHITE,
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 13.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                InspectorTextTag,
+            ));
+        });
+
+    // Time controls
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    ..default()
                 },
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new(
+                        " epoch ",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 13.0,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    TextSection::new(
+                        " rate ",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 13.0,
+                            color: Color::YELLOW,
+                        },
+                    ),
+                ]),
+                EpochTextTag,
             ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (label, button) in [
+                        ("|<", TimeButton::SeekStart),
+                        ("-100 Myr", TimeButton::SeekBackward),
+                        ("Pause", TimeButton::Pause),
+                        ("Reverse", TimeButton::Reverse),
+                        ("Slower", TimeButton::Slower),
+                        ("Faster", TimeButton::Faster),
+                        ("+100 Myr", TimeButton::SeekForward),
+                        ("Set", TimeButton::SetEpoch),
+                    ] {
+                        spawn_button(parent, &font, label, button);
+                    }
+                });
+
+            // Catalog export
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for format in ExportFormat::ALL {
+                        let label = format!("Export {}", format.extension().to_uppercase());
+                        spawn_button(parent, &font, &label, ExportButton(format));
+                    }
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 13.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        ExportStatusTextTag,
+                    ));
+                });
+
+            // Camera path recording and playback
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (label, button) in [
+                        ("Keyframe", CameraPathButton::Keyframe),
+                        ("Play", CameraPathButton::Play),
+                        ("Stop", CameraPathButton::Stop),
+                        ("Save Path", CameraPathButton::Save),
+                    ] {
+                        spawn_button(parent, &font, label, button);
+                    }
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 13.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        CameraPathStatusTextTag,
+                    ));
+                });
+
+            // Frame sequence for video, at a fixed timestep
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (label, button) in [
+                        ("Record Frames", FrameRecordButton::Toggle),
+                        ("Record Path", FrameRecordButton::CameraPath),
+                    ] {
+                        spawn_button(parent, &font, label, button);
+                    }
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 13.0,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        FrameStatusTextTag,
+                    ));
+                });
         });
+}
+
+fn spawn_settings_panel(commands: &mut Commands, font: &Handle<Font>) {
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 13.0,
+        color: Color::WHITE,
+    };
+
     commands
         .spawn((
-            ButtonBundle {
+            NodeBundle {
                 style: Style {
-                    size: Size::new(Val::Px(100.0), Val::Px(40.0)),
+                    position_type: PositionType::Absolute,
                     position: UiRect {
                         top: Val::Px(10.0),
-                        left: Val::Px(20.0),
+                        left: Val::Px(10.0),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            Interaction::default(),
+            SettingsPanelTag,
+        ))
+        .with_children(|parent| {
+            // Page tabs
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for page in SettingsPage::ALL {
+                        spawn_button(parent, font, page.label(), SettingsPageButton(page));
+                    }
+                    spawn_button(parent, font, "Hide", SettingsHideButtonTag);
+                });
+
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        focus_policy: FocusPolicy::Pass,
                         ..default()
                     },
+                    SettingsBodyTag,
+                ))
+                .with_children(|parent| {
+                    for field in SettingField::ALL {
+                        spawn_setting_row(parent, font, &text_style, field);
+                    }
+                    spawn_presets_page(parent, font, &text_style);
+                });
+        });
+}
+
+fn spawn_presets_page(parent: &mut ChildBuilder, font: &Handle<Font>, text_style: &TextStyle) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    display: Display::None,
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                ..default()
+            },
+            SettingRow(SettingsPage::Presets),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_button(parent, font, "Save", PresetActionButton::Save);
+                    spawn_button(parent, font, "Save As", PresetActionButton::SaveAs);
+                    spawn_button(parent, font, "Rename", PresetActionButton::Rename);
+                    spawn_button(parent, font, "Refresh", PresetActionButton::Refresh);
+                });
+
+            parent.spawn((
+                TextBundle::from_section("", text_style.clone()),
+                PresetStatusTextTag,
+            ));
+
+            // Filled by the preset system
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                },
+                PresetListTag,
+            ));
+
+            // Bookmarks are saved with the preset
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_button(parent, font, "Bookmark View", BookmarkButton::Add);
+                });
+
+            // Filled by the bookmark system
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                },
+                BookmarkListTag,
+            ));
+
+            // Saved camera paths, loaded to be played
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_button(parent, font, "Refresh Paths", CameraPathButton::Refresh);
+                });
+
+            // Filled by the camera path system
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                },
+                CameraPathListTag,
+            ));
+
+            // Snapshots keep the objects themselves, not only the settings
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_button(parent, font, "Save Snapshot", SnapshotActionButton::Save);
+                    spawn_button(parent, font, "Refresh", SnapshotActionButton::Refresh);
+                });
+
+            parent.spawn((
+                TextBundle::from_section("", text_style.clone()),
+                SnapshotStatusTextTag,
+            ));
+
+            // Filled by the snapshot system
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                },
+                SnapshotListTag,
+            ));
+        });
+}
+
+fn spawn_setting_row(
+    parent: &mut ChildBuilder,
+    font: &Handle<Font>,
+    text_style: &TextStyle,
+    field: SettingField,
+) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    display: Display::None,
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                ..default()
+            },
+            SettingRow(field.page()),
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(field.label(), text_style.clone()).with_style(Style {
+                    size: Size::new(Val::Px(140.0), Val::Auto),
+                    ..default()
+                }),
+            );
+
+            match field.widget() {
+                FieldWidget::Toggle => {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(50.0), Val::Px(20.0)),
+                                    margin: UiRect::all(Val::Px(2.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: Color::rgba(1., 1., 1., 0.3).into(),
+                                ..default()
+                            },
+                            SettingToggleButton(field),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section("", text_style.clone()),
+                                SettingValueText(field),
+                            ));
+                        });
+                }
+                FieldWidget::Entry => spawn_setting_entry(parent, text_style, field),
+                FieldWidget::Slider | FieldWidget::Stepper => {
+                    spawn_button(
+                        parent,
+                        font,
+                        "-",
+                        SettingStepButton {
+                            field,
+                            direction: -1.,
+                        },
+                    );
+
+                    if field.widget() == FieldWidget::Slider {
+                        parent
+                            .spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Px(120.0), Val::Px(12.0)),
+                                        margin: UiRect::horizontal(Val::Px(4.0)),
+                                        ..default()
+                                    },
+                                    background_color: Color::rgba(1., 1., 1., 0.2).into(),
+                                    ..default()
+                                },
+                                Interaction::default(),
+                                SettingSlider(field),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            size: Size::new(Val::Percent(0.), Val::Percent(100.)),
+                                            ..default()
+                                        },
+                                        background_color: Color::rgba(0.35, 0.75, 0.35, 0.8).into(),
+                                        focus_policy: FocusPolicy::Pass,
+                                        ..default()
+                                    },
+                                    SettingSliderFill(field),
+                                ));
+                            });
+                    }
+
+                    spawn_button(
+                        parent,
+                        font,
+                        "+",
+                        SettingStepButton {
+                            field,
+                            direction: 1.,
+                        },
+                    );
+
+                    if field.has_text_entry() {
+                        spawn_setting_entry(parent, text_style, field);
+                    } else {
+                        parent.spawn((
+                            TextBundle::from_section("", text_style.clone()).with_style(Style {
+                                size: Size::new(Val::Px(80.0), Val::Auto),
+                                margin: UiRect::left(Val::Px(4.0)),
+                                ..default()
+                            }),
+                            SettingValueText(field),
+                        ));
+                    }
+                }
+            }
+        });
+}
+
+fn spawn_setting_entry(parent: &mut ChildBuilder, text_style: &TextStyle, field: SettingField) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Px(170.0), Val::Px(20.0)),
+                    margin: UiRect::left(Val::Px(4.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(1., 1., 1., 0.3).into(),
+                ..default()
+            },
+            SettingEntryButton(field),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("", text_style.clone()),
+                SettingValueText(field),
+            ));
+        });
+}
+
+pub fn spawn_button(
+    parent: &mut ChildBuilder,
+    font: &Handle<Font>,
+    label: &str,
+    tag: impl Component,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Auto, Val::Px(24.0)),
+                    margin: UiRect::all(Val::Px(2.0)),
+                    padding: UiRect::horizontal(Val::Px(6.0)),
                     // horizontally center child text
                     justify_content: JustifyContent::Center,
                     // vertically center child text
@@ -96,11 +664,11 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                 background_color: Color::rgba(1., 1., 1., 0.3).into(),
                 ..default()
             },
-            StarDownButtonTag,
+            tag,
         ))
         .with_children(|parent| {
             parent.spawn(TextBundle::from_section(
-                "- 1000 stars",
+                label,
                 TextStyle {
                     font: font.clone(),
                     font_size: 13.0,
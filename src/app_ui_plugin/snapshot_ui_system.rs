@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use galaxy::snapshot::Snapshot;
+
+use super::app_plugin::config_problems_resource::ConfigProblems;
+use super::app_plugin::settings_resource::{DensityWaveResource, GalaxySettingsResource};
+use super::app_plugin::simulation_time_resource::SimulationTime;
+use super::app_plugin::snapshot_resource::SnapshotLibrary;
+use super::app_plugin::star_component::{self, KindComponent, ObjectIndex, StarComponent};
+use super::components::{
+    SnapshotActionButton, SnapshotListTag, SnapshotLoadButton, SnapshotStatusTextTag,
+};
+use super::setup_system::spawn_button;
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_snapshot_buttons(
+    action_query: Query<
+        (&Interaction, &SnapshotActionButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    load_query: Query<(&Interaction, &SnapshotLoadButton), (Changed<Interaction>, With<Button>)>,
+    stars: Query<(&StarComponent, &KindComponent, &ObjectIndex)>,
+    mut library: ResMut<SnapshotLibrary>,
+    mut galaxy_settings: ResMut<GalaxySettingsResource>,
+    mut density_wave: ResMut<DensityWaveResource>,
+    mut sim_time: ResMut<SimulationTime>,
+    mut config_problems: ResMut<ConfigProblems>,
+) {
+    for (interaction, action) in &action_query {
+        if *interaction == Interaction::Clicked {
+            match action {
+                SnapshotActionButton::Save => {
+                    let snapshot = Snapshot {
+                        galaxy_settings: galaxy_settings.0.clone(),
+                        density_wave: density_wave.0.clone(),
+                        years: sim_time.years,
+                        objects: star_component::galaxy_objects(stars.iter()),
+                    };
+                    library.save(&snapshot);
+                }
+                SnapshotActionButton::Refresh => library.refresh(),
+            }
+        }
+    }
+
+    for (interaction, load_button) in &load_query {
+        if *interaction == Interaction::Clicked {
+            if let Some(snapshot) = library.load(&load_button.0) {
+                **galaxy_settings = snapshot.galaxy_settings.clone();
+                **density_wave = snapshot.density_wave.clone();
+                sim_time.seek(snapshot.years);
+                library.pending = Some(snapshot);
+                // The snapshot replaces all settings, older problems no longer apply
+                config_problems.clear();
+            }
+        }
+    }
+}
+
+// Rebuild the snapshot browser when the library changes
+pub fn update_snapshot_list(
+    mut commands: Commands,
+    library: Res<SnapshotLibrary>,
+    asset_server: Res<AssetServer>,
+    list_query: Query<Entity, With<SnapshotListTag>>,
+    mut status_query: Query<&mut Text, With<SnapshotStatusTextTag>>,
+) {
+    if !library.is_changed() {
+        return;
+    }
+
+    for mut text in &mut status_query {
+        text.sections[0].value = library.status.clone();
+    }
+
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    for list in &list_query {
+        commands.entity(list).despawn_descendants();
+        commands.entity(list).with_children(|parent| {
+            for name in &library.names {
+                spawn_button(parent, &font, name, SnapshotLoadButton(name.clone()));
+            }
+        });
+    }
+}
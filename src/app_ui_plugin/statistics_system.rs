@@ -3,7 +3,7 @@ use bevy::{
     prelude::*,
 };
 
-use super::{app_plugin::galaxy_setting_component::GalaxySettings, components::FpsTag};
+use super::{app_plugin::settings_resource::GalaxySettingsResource, components::FpsTag};
 
 pub fn update_fps(diagnostics: Res<Diagnostics>, mut query: Query<&mut Text, With<FpsTag>>) {
     for mut text in &mut query {
@@ -19,7 +19,7 @@ pub fn update_fps(diagnostics: Res<Diagnostics>, mut query: Query<&mut Text, Wit
 }
 
 pub fn update_stars_count(
-    galaxy_settings: Res<GalaxySettings>,
+    galaxy_settings: Res<GalaxySettingsResource>,
     mut query: Query<&mut Text, With<FpsTag>>,
 ) {
     if !galaxy_settings.is_changed() {
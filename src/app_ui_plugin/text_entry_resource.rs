@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use super::setting_field::SettingField;
+
+/// Field of the UI text can be typed into
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EntryTarget {
+    Setting(SettingField),
+    /// Name to save the preset as
+    PresetName,
+    /// New name of the current preset
+    PresetRename,
+    /// New name of the bookmark at the index
+    BookmarkName(usize),
+    /// Epoch to jump to
+    Epoch,
+}
+
+/// Text typed into a field of the UI. While a field is edited the keyboard
+/// goes to it and not to the shortcuts.
+#[derive(Resource, Default)]
+pub struct TextEntry {
+    pub target: Option<EntryTarget>,
+    pub text: String,
+    /// Text confirmed with Enter, taken by the UI of the target
+    pub submitted: Option<(EntryTarget, String)>,
+}
+
+impl TextEntry {
+    pub fn start(&mut self, target: EntryTarget) {
+        self.target = Some(target);
+        self.text.clear();
+    }
+
+    pub fn is_editing(&self, target: EntryTarget) -> bool {
+        self.target == Some(target)
+    }
+
+    /// Text shown in the field while it is edited
+    pub fn display(&self) -> String {
+        format!("{}_", self.text)
+    }
+}
@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use super::text_entry_resource::TextEntry;
+
+// Runs before the systems reading the keyboard, they don't see the typed keys
+pub fn update_text_entry(
+    mut entry: ResMut<TextEntry>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut keyboard: ResMut<Input<KeyCode>>,
+) {
+    let Some(target) = entry.target else {
+        characters.clear();
+        return;
+    };
+
+    for character in characters.iter() {
+        if !character.char.is_control() {
+            entry.text.push(character.char);
+        }
+    }
+    if keyboard.just_pressed(KeyCode::Back) {
+        entry.text.pop();
+    }
+    if keyboard.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter]) {
+        let text = std::mem::take(&mut entry.text);
+        entry.submitted = Some((target, text));
+        entry.target = None;
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        entry.target = None;
+        entry.text.clear();
+    }
+    keyboard.reset_all();
+}
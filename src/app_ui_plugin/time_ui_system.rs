@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use super::app_plugin::settings_resource::GalaxySettingsResource;
+use super::app_plugin::simulation_time_resource::{SimulationTime, TimeControl};
+use super::components::{EpochTextTag, TimeButton};
+use super::text_entry_resource::{EntryTarget, TextEntry};
+
+const SEEK_STEP_YEARS: f64 = 100_000_000.;
+
+pub fn update_time_buttons(
+    interaction_query: Query<(&Interaction, &TimeButton), (Changed<Interaction>, With<Button>)>,
+    mut time_control: ResMut<TimeControl>,
+    mut sim_time: ResMut<SimulationTime>,
+    mut entry: ResMut<TextEntry>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Clicked {
+            match button {
+                TimeButton::Pause => time_control.toggle_pause(),
+                TimeButton::Reverse => time_control.toggle_reverse(),
+                TimeButton::Slower => time_control.slower(),
+                TimeButton::Faster => time_control.faster(),
+                TimeButton::SeekStart => sim_time.seek(0.),
+                TimeButton::SeekBackward => {
+                    let years = sim_time.years - SEEK_STEP_YEARS;
+                    sim_time.seek(years);
+                }
+                TimeButton::SeekForward => {
+                    let years = sim_time.years + SEEK_STEP_YEARS;
+                    sim_time.seek(years);
+                }
+                TimeButton::SetEpoch => entry.start(EntryTarget::Epoch),
+            }
+        }
+    }
+
+    if let Some((EntryTarget::Epoch, text)) = entry.submitted.clone() {
+        entry.submitted = None;
+        // Text that doesn't parse is dropped, the epoch stays
+        if let Some(years) = parse_years(&text) {
+            sim_time.seek(years);
+        }
+    }
+}
+
+pub fn update_epoch_text(
+    sim_time: Res<SimulationTime>,
+    time_control: Res<TimeControl>,
+    galaxy_settings: Res<GalaxySettingsResource>,
+    entry: Res<TextEntry>,
+    mut query: Query<&mut Text, With<EpochTextTag>>,
+) {
+    if !sim_time.is_changed() && !time_control.is_changed() && !entry.is_changed() {
+        return;
+    }
+
+    // Express the epoch in orbits of a star half way to the galaxy edge as well
+    let reference_radius = galaxy_settings.radius / 2.;
+    let orbital_period = galaxy_settings.get_orbital_period(reference_radius);
+
+    let state = if time_control.is_paused {
+        "paused"
+    } else if time_control.is_reversed {
+        "reverse"
+    } else {
+        "forward"
+    };
+
+    for mut text in &mut query {
+        text.sections[0].value = if entry.is_editing(EntryTarget::Epoch) {
+            format!("Epoch: {} (yr, kyr, Myr or Gyr)\n", entry.display())
+        } else {
+            format!(
+                "Epoch: {} ({:.2} orbits at {:.0} pc)\n",
+                format_years(sim_time.years),
+                sim_time.years / orbital_period,
+                reference_radius,
+            )
+        };
+        text.sections[1].value = format!(
+            "Rate: {}/s, {}",
+            format_years(time_control.years_per_second),
+            state
+        );
+    }
+}
+
+pub fn format_years(years: f64) -> String {
+    let abs = years.abs();
+    if abs >= 1e9 {
+        format!("{:.3} Gyr", years / 1e9)
+    } else if abs >= 1e6 {
+        format!("{:.2} Myr", years / 1e6)
+    } else if abs >= 1e3 {
+        format!("{:.1} kyr", years / 1e3)
+    } else {
+        format!("{:.0} yr", years)
+    }
+}
+
+/// Reads an epoch written like `format_years` does, years without a unit
+fn parse_years(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let (number, unit) = match text.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(index) => text.split_at(index),
+        None => (text, "yr"),
+    };
+    let scale = match unit.trim() {
+        "yr" => 1.,
+        "kyr" => 1e3,
+        "Myr" => 1e6,
+        "Gyr" => 1e9,
+        _ => return None,
+    };
+    let years = number.trim().parse::<f64>().ok()? * scale;
+    years.is_finite().then_some(years)
+}
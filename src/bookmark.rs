@@ -0,0 +1,41 @@
+//! Named camera views, saved with a preset to come back to the same regions.
+
+use bevy_math::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Center of the view in world units and the projection scale
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmark {
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+}
+
+impl CameraBookmark {
+    pub fn new(name: &str, center: Vec2, scale: f32) -> Self {
+        CameraBookmark {
+            name: name.to_string(),
+            x: center.x,
+            y: center.y,
+            scale,
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    /// A bookmark that can be flown to, with a finite center and a positive scale
+    pub fn is_valid(&self) -> bool {
+        self.center().is_finite() && self.scale.is_finite() && self.scale > 0.
+    }
+}
+
+/// First name of the form `view_1` not used by the bookmarks
+pub fn next_free_name(bookmarks: &[CameraBookmark]) -> String {
+    (1..)
+        .map(|i| format!("view_{}", i))
+        .find(|name| bookmarks.iter().all(|bookmark| &bookmark.name != name))
+        .unwrap()
+}
@@ -0,0 +1,238 @@
+//! Camera keyframes for fly-throughs, played back along a spline.
+//!
+//! Center and log scale follow a cubic Hermite spline through the keyframes,
+//! at rest on the first and last one. The epoch is interpolated linearly,
+//! so the simulated time never runs back between two keyframes going forward.
+
+use bevy_math::Vec2;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::preset;
+
+pub const CAMERA_PATHS_DIR: &str = "camera_paths";
+const CAMERA_PATH_EXTENSION: &str = "json";
+
+/// View at a moment of the path, `time` in seconds from its start
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    pub time: f32,
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+    pub years: f64,
+}
+
+/// Keyframes ordered by time
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CameraPath {
+    pub keyframes: Vec<Keyframe>,
+}
+
+/// Where the camera is at a moment of the path
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraState {
+    pub center: Vec2,
+    pub scale: f32,
+    pub years: f64,
+}
+
+#[derive(Debug)]
+pub enum CameraPathError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for CameraPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CameraPathError::Io(err) => write!(f, "{}", err),
+            CameraPathError::Json(err) => write!(f, "{}", err),
+            CameraPathError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CameraPathError {}
+
+impl From<io::Error> for CameraPathError {
+    fn from(err: io::Error) -> Self {
+        CameraPathError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CameraPathError {
+    fn from(err: serde_json::Error) -> Self {
+        CameraPathError::Json(err)
+    }
+}
+
+impl Keyframe {
+    pub fn new(time: f32, center: Vec2, scale: f32, years: f64) -> Self {
+        Keyframe {
+            time,
+            x: center.x,
+            y: center.y,
+            scale,
+            years,
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+impl CameraPath {
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keyframes.len()
+    }
+
+    /// Seconds from the first to the last keyframe
+    pub fn duration(&self) -> f32 {
+        match (self.keyframes.first(), self.keyframes.last()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.,
+        }
+    }
+
+    /// Add a keyframe at the end, one at the same time as the last is replaced
+    pub fn push(&mut self, keyframe: Keyframe) {
+        if let Some(last) = self.keyframes.last_mut() {
+            if keyframe.time <= last.time {
+                *last = Keyframe {
+                    time: last.time,
+                    ..keyframe
+                };
+                return;
+            }
+        }
+        self.keyframes.push(keyframe);
+    }
+
+    /// A path that can be played: finite values, positive scales, times increasing
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, keyframe) in self.keyframes.iter().enumerate() {
+            let is_valid = keyframe.time.is_finite()
+                && keyframe.center().is_finite()
+                && keyframe.years.is_finite()
+                && keyframe.scale.is_finite()
+                && keyframe.scale > 0.;
+            if !is_valid {
+                return Err(format!(
+                    "keyframe {} needs finite values and a positive scale",
+                    i
+                ));
+            }
+        }
+        match self
+            .keyframes
+            .windows(2)
+            .position(|pair| pair[1].time <= pair[0].time)
+        {
+            Some(i) => Err(format!("keyframe {} is not after the one before", i + 1)),
+            None => Ok(()),
+        }
+    }
+
+    /// The view `time` seconds after the first keyframe, held at the ends
+    pub fn sample(&self, time: f32) -> Option<CameraState> {
+        let first = self.keyframes.first()?;
+        let time = first.time + time.max(0.);
+        let next = self
+            .keyframes
+            .iter()
+            .position(|keyframe| keyframe.time > time);
+        let (i, next) = match next {
+            Some(0) => return Some(state_of(first)),
+            Some(next) => (next - 1, next),
+            None => return self.keyframes.last().map(state_of),
+        };
+
+        let (from, to) = (&self.keyframes[i], &self.keyframes[next]);
+        let dt = to.time - from.time;
+        let s = (time - from.time) / dt;
+        let spline = |value: fn(&Keyframe) -> f32| {
+            hermite(
+                value(from),
+                self.tangent(i, value),
+                value(to),
+                self.tangent(next, value),
+                dt,
+                s,
+            )
+        };
+
+        Some(CameraState {
+            center: Vec2::new(spline(|k| k.x), spline(|k| k.y)),
+            scale: spline(|k| k.scale.ln()).exp(),
+            years: from.years + (to.years - from.years) * s as f64,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), CameraPathError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<CameraPath, CameraPathError> {
+        let file = fs::read_to_string(path)?;
+        let camera_path: CameraPath = serde_json::from_str(&file)?;
+        camera_path.validate().map_err(CameraPathError::Invalid)?;
+        Ok(camera_path)
+    }
+
+    /// Slope at a keyframe from its neighbours, zero at the ends
+    fn tangent(&self, i: usize, value: fn(&Keyframe) -> f32) -> f32 {
+        if i == 0 || i + 1 >= self.keyframes.len() {
+            return 0.;
+        }
+        let (before, after) = (&self.keyframes[i - 1], &self.keyframes[i + 1]);
+        (value(after) - value(before)) / (after.time - before.time)
+    }
+}
+
+fn state_of(keyframe: &Keyframe) -> CameraState {
+    CameraState {
+        center: keyframe.center(),
+        scale: keyframe.scale,
+        years: keyframe.years,
+    }
+}
+
+/// Cubic Hermite between `p0` and `p1` with slopes per second, `s` from 0 to 1 over `dt`
+fn hermite(p0: f32, m0: f32, p1: f32, m1: f32, dt: f32, s: f32) -> f32 {
+    let s2 = s * s;
+    let s3 = s2 * s;
+    (2. * s3 - 3. * s2 + 1.) * p0
+        + (s3 - 2. * s2 + s) * dt * m0
+        + (-2. * s3 + 3. * s2) * p1
+        + (s3 - s2) * dt * m1
+}
+
+// Not `with_extension`, it would replace the end of names with a dot like `m.31`
+pub fn camera_path_file(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.{}", name, CAMERA_PATH_EXTENSION))
+}
+
+/// Names of all camera paths in the directory, sorted
+pub fn list_camera_paths(dir: &Path) -> Vec<String> {
+    preset::list_names(dir, CAMERA_PATH_EXTENSION)
+}
+
+/// First free name of the form `path_001`
+pub fn next_free_name(dir: &Path) -> String {
+    preset::next_free_name_with_prefix(dir, CAMERA_PATH_EXTENSION, "path")
+}
@@ -0,0 +1,33 @@
+use galaxy::export::{self, ExportFormat};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use super::settings_loader::load_galaxy;
+use super::ExportArgs;
+
+pub fn run(args: &ExportArgs) -> Result<(), String> {
+    let galaxy = load_galaxy(&args.settings)?;
+    let objects = &galaxy.objects;
+    let years = args.years.unwrap_or(galaxy.years);
+
+    let format = args
+        .format
+        .or_else(|| args.output.as_deref().and_then(ExportFormat::from_path))
+        .unwrap_or(ExportFormat::Csv);
+    let write = |writer: &mut dyn Write| {
+        let mut writer = BufWriter::new(writer);
+        export::write(&mut writer, format, objects, &galaxy.galaxy_settings, years)?;
+        writer.flush()
+    };
+
+    let result = match &args.output {
+        Some(path) => File::create(path).and_then(|mut file| write(&mut file)),
+        None => write(&mut io::stdout().lock()),
+    };
+    result.map_err(|err| format!("unable to export: {}", err))?;
+
+    if let Some(path) = &args.output {
+        eprintln!("{} objects written to {}", objects.len(), path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use bevy::math::Vec2;
+use galaxy::camera_path::CameraPath;
+use galaxy::frames::{self, FrameClock};
+use galaxy::render::{self, Particle, View};
+use std::fs;
+
+use super::settings_loader::load_galaxy;
+use super::FramesArgs;
+
+pub fn run(args: &FramesArgs) -> Result<(), String> {
+    if !(args.fps.is_finite() && args.fps > 0.) {
+        return Err(format!("fps has to be positive, got {}", args.fps));
+    }
+    let galaxy = load_galaxy(&args.settings)?;
+    let camera_path = match &args.camera_path {
+        Some(path) => Some(
+            CameraPath::load(path)
+                .map_err(|err| format!("unable to read {}: {}", path.display(), err))?,
+        ),
+        None => None,
+    };
+
+    let clock = FrameClock {
+        fps: args.fps,
+        start_years: args.years.unwrap_or(galaxy.years),
+        years_per_second: args.years_per_second,
+    };
+    let count = match (args.frames, &camera_path) {
+        (Some(count), _) => count,
+        (None, Some(camera_path)) => clock.frame_count(camera_path.duration()),
+        (None, None) => return Err("set --frames or --camera-path".to_string()),
+    };
+    let center = match args.center.as_deref() {
+        Some([x, y]) => Vec2::new(*x, *y),
+        _ => Vec2::ZERO,
+    };
+
+    fs::create_dir_all(&args.output)
+        .map_err(|err| format!("unable to create {}: {}", args.output.display(), err))?;
+    let particle = Particle::default();
+
+    for frame in 0..count {
+        // The camera path has its own epochs, as they were recorded
+        let view = match camera_path
+            .as_ref()
+            .and_then(|camera_path| camera_path.sample(clock.seconds(frame)))
+        {
+            Some(state) => View {
+                width: args.width,
+                height: args.height,
+                center: state.center,
+                scale: state.scale,
+                years: state.years,
+            },
+            None => View {
+                width: args.width,
+                height: args.height,
+                center,
+                scale: args.scale,
+                years: clock.years(frame),
+            },
+        };
+        let bloom = args.bloom.bloom(view.scale);
+        let bloom = (bloom.intensity > 0.).then_some(&bloom);
+
+        let image = render::render(
+            &galaxy.objects,
+            &galaxy.galaxy_settings,
+            &view,
+            bloom,
+            &particle,
+        );
+        let path = frames::frame_path(&args.output, frame);
+        image
+            .save(&path)
+            .map_err(|err| format!("unable to write {}: {}", path.display(), err))?;
+        eprint!("\rframe {}/{}", frame + 1, count);
+    }
+    eprintln!();
+    println!("{} frames written to {}", count, args.output.display());
+    Ok(())
+}
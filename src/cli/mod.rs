@@ -0,0 +1,249 @@
+use clap::{Args, Parser, Subcommand};
+use galaxy::export::ExportFormat;
+use galaxy::overrides::{Override, Overrides, Section};
+use galaxy::render::Bloom;
+use std::path::PathBuf;
+
+use super::app_plugin;
+
+pub mod export_command;
+pub mod frames_command;
+pub mod render_command;
+pub mod settings_loader;
+pub mod snapshot_command;
+pub mod view_command;
+
+/// Procedural spiral galaxy: interactive viewer, offline renderer and catalog export
+#[derive(Parser)]
+#[command(version, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Without a subcommand the viewer is opened with these arguments
+    #[command(flatten)]
+    pub view: ViewArgs,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Open the interactive viewer, the default without a subcommand
+    View(ViewArgs),
+    /// Render an image without a window or GPU
+    Render(RenderArgs),
+    /// Write the generated objects as CSV or JSON Lines
+    Export(ExportArgs),
+    /// Save the galaxy as a binary snapshot, to be loaded with `--snapshot`
+    Snapshot(SnapshotArgs),
+    /// Render a numbered PNG sequence at a fixed timestep without a window or GPU
+    Frames(FramesArgs),
+}
+
+/// Where the settings come from and what to change in them
+#[derive(Args)]
+pub struct SettingsArgs {
+    /// Snapshot with the settings and every object, instead of generating them
+    #[arg(long, conflicts_with_all = ["settings", "density_wave", "seed", "stars", "dusts", "filaments", "h2", "h2_cores", "fields"])]
+    pub snapshot: Option<PathBuf>,
+    /// Galaxy settings json [default: assets/galaxy_settings.json]
+    #[arg(long)]
+    pub settings: Option<PathBuf>,
+    /// Density wave json [default: assets/density_wave.json]
+    #[arg(long)]
+    pub density_wave: Option<PathBuf>,
+    /// Random seed of the generation
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Number of stars
+    #[arg(long)]
+    pub stars: Option<usize>,
+    /// Number of dust particles
+    #[arg(long)]
+    pub dusts: Option<usize>,
+    /// Number of dust filaments
+    #[arg(long)]
+    pub filaments: Option<usize>,
+    /// Number of H2 regions
+    #[arg(long)]
+    pub h2: Option<usize>,
+    /// Number of H2 region cores
+    #[arg(long)]
+    pub h2_cores: Option<usize>,
+    /// Override any field, e.g. `radius=15000` or `wave.k=0.03`. Repeatable.
+    #[arg(long = "set", value_name = "FIELD=VALUE")]
+    pub fields: Vec<Override>,
+}
+
+impl SettingsArgs {
+    /// Seed and counts first, so `--set` can still change them
+    pub fn overrides(&self) -> Overrides {
+        let mut overrides = vec![];
+        if let Some(seed) = self.seed {
+            overrides.push(Override::new(Section::GalaxySettings, "seed", seed));
+        }
+        for (field, count) in [
+            ("count_stars", self.stars),
+            ("count_dusts", self.dusts),
+            ("count_dusts_filaments", self.filaments),
+            ("count_h2", self.h2),
+            ("count_h2_core", self.h2_cores),
+        ] {
+            if let Some(count) = count {
+                overrides.push(Override::new(Section::GalaxySettings, field, count));
+            }
+        }
+        overrides.extend(self.fields.iter().cloned());
+        Overrides(overrides)
+    }
+}
+
+/// Bloom of the camera, defaults to the one of the application
+#[derive(Args)]
+pub struct BloomArgs {
+    /// Brightness from which pixels start to glow
+    #[arg(long)]
+    pub bloom_threshold: Option<f32>,
+    /// Strength of the glow, 0 disables bloom
+    #[arg(long)]
+    pub bloom_intensity: Option<f32>,
+    /// Spread of the glow
+    #[arg(long)]
+    pub bloom_scale: Option<f32>,
+}
+
+impl BloomArgs {
+    pub fn bloom(&self, camera_scale: f32) -> Bloom {
+        let bloom = Bloom::for_scale(camera_scale);
+        Bloom {
+            threshold: self.bloom_threshold.unwrap_or(bloom.threshold),
+            intensity: self.bloom_intensity.unwrap_or(bloom.intensity),
+            scale: self.bloom_scale.unwrap_or(bloom.scale),
+            ..bloom
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ViewArgs {
+    #[command(flatten)]
+    pub settings: SettingsArgs,
+    #[command(flatten)]
+    pub bloom: BloomArgs,
+    /// Initial zoom in world units per pixel
+    #[arg(long, default_value_t = 70., value_parser = positive)]
+    pub scale: f32,
+    /// How fast the view slows down after a drag, higher stops sooner
+    #[arg(long, default_value_t = 5.)]
+    pub pan_friction: f32,
+    /// Stop panning as soon as the mouse button is released
+    #[arg(long)]
+    pub no_inertia: bool,
+    /// How fast wheel zooming catches up, higher is snappier
+    #[arg(long, default_value_t = 12.)]
+    pub zoom_smoothing: f32,
+    /// Zoom in steps with the mouse wheel, without easing
+    #[arg(long)]
+    pub no_smooth_zoom: bool,
+    /// Epoch in years to start at [default: the one of the snapshot or 0]
+    #[arg(long, allow_negative_numbers = true)]
+    pub years: Option<f64>,
+}
+
+#[derive(Args)]
+pub struct RenderArgs {
+    #[command(flatten)]
+    pub settings: SettingsArgs,
+    #[command(flatten)]
+    pub bloom: BloomArgs,
+    /// Output PNG image
+    #[arg(short, long, default_value = "galaxy.png")]
+    pub output: PathBuf,
+    /// Image width in pixels
+    #[arg(long, default_value_t = 1920, value_parser = clap::value_parser!(u32).range(1..))]
+    pub width: u32,
+    /// Image height in pixels
+    #[arg(long, default_value_t = 1080, value_parser = clap::value_parser!(u32).range(1..))]
+    pub height: u32,
+    /// Zoom in world units per pixel
+    #[arg(long, default_value_t = 70., value_parser = positive)]
+    pub scale: f32,
+    /// World position in the middle of the image
+    #[arg(long, num_args = 2, value_names = ["X", "Y"], allow_negative_numbers = true)]
+    pub center: Option<Vec<f32>>,
+    /// Epoch in years [default: the one of the snapshot or 0]
+    #[arg(long, allow_negative_numbers = true)]
+    pub years: Option<f64>,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub settings: SettingsArgs,
+    /// Output file, standard output if not set
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// csv or jsonl [default: from the output extension, csv otherwise]
+    #[arg(long)]
+    pub format: Option<ExportFormat>,
+    /// Epoch in years of the exported positions [default: the one of the snapshot or 0]
+    #[arg(long, allow_negative_numbers = true)]
+    pub years: Option<f64>,
+}
+
+#[derive(Args)]
+pub struct SnapshotArgs {
+    #[command(flatten)]
+    pub settings: SettingsArgs,
+    /// Output snapshot
+    #[arg(short, long, default_value = "galaxy.snapshot")]
+    pub output: PathBuf,
+    /// Epoch in years stored in the snapshot [default: the one of the snapshot or 0]
+    #[arg(long, allow_negative_numbers = true)]
+    pub years: Option<f64>,
+}
+
+#[derive(Args)]
+pub struct FramesArgs {
+    #[command(flatten)]
+    pub settings: SettingsArgs,
+    #[command(flatten)]
+    pub bloom: BloomArgs,
+    /// Directory of the frames
+    #[arg(short, long, default_value = "frames")]
+    pub output: PathBuf,
+    /// Number of frames [default: enough for the camera path]
+    #[arg(long)]
+    pub frames: Option<u32>,
+    /// Frames per second of video
+    #[arg(long, default_value_t = 30.)]
+    pub fps: f32,
+    /// Simulated years per second of video, without a camera path
+    #[arg(long, default_value_t = 1_000_000., allow_negative_numbers = true)]
+    pub years_per_second: f64,
+    /// Camera path to fly along, its keyframes set the view and the epoch
+    #[arg(long)]
+    pub camera_path: Option<PathBuf>,
+    /// Image width in pixels
+    #[arg(long, default_value_t = 1920, value_parser = clap::value_parser!(u32).range(1..))]
+    pub width: u32,
+    /// Image height in pixels
+    #[arg(long, default_value_t = 1080, value_parser = clap::value_parser!(u32).range(1..))]
+    pub height: u32,
+    /// Zoom in world units per pixel, without a camera path
+    #[arg(long, default_value_t = 70., value_parser = positive)]
+    pub scale: f32,
+    /// World position in the middle of the images, without a camera path
+    #[arg(long, num_args = 2, value_names = ["X", "Y"], allow_negative_numbers = true)]
+    pub center: Option<Vec<f32>>,
+    /// Epoch in years of the first frame [default: the one of the snapshot or 0]
+    #[arg(long, allow_negative_numbers = true)]
+    pub years: Option<f64>,
+}
+
+fn positive(text: &str) -> Result<f32, String> {
+    let value: f32 = text.parse().map_err(|err| format!("{}", err))?;
+    if value.is_finite() && value > 0. {
+        Ok(value)
+    } else {
+        Err(format!("has to be positive, got {}", value))
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::math::Vec2;
+use galaxy::render::{self, Particle, View};
+
+use super::settings_loader::load_galaxy;
+use super::RenderArgs;
+
+pub fn run(args: &RenderArgs) -> Result<(), String> {
+    let galaxy = load_galaxy(&args.settings)?;
+
+    let center = match args.center.as_deref() {
+        Some([x, y]) => Vec2::new(*x, *y),
+        _ => Vec2::ZERO,
+    };
+    let view = View {
+        width: args.width,
+        height: args.height,
+        center,
+        scale: args.scale,
+        years: args.years.unwrap_or(galaxy.years),
+    };
+    let bloom = args.bloom.bloom(args.scale);
+    let bloom = (bloom.intensity > 0.).then_some(&bloom);
+
+    let image = render::render(
+        &galaxy.objects,
+        &galaxy.galaxy_settings,
+        &view,
+        bloom,
+        &Particle::default(),
+    );
+    image
+        .save(&args.output)
+        .map_err(|err| format!("unable to write {}: {}", args.output.display(), err))?;
+    println!("{} written", args.output.display());
+    Ok(())
+}
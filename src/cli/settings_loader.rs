@@ -0,0 +1,82 @@
+use galaxy::density_wave::DensityWave;
+use galaxy::galaxy_setting_component::GalaxySettings;
+use galaxy::generator;
+use galaxy::snapshot::Snapshot;
+use galaxy::validation::{self, ConfigProblem};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::SettingsArgs;
+
+const GALAXY_SETTINGS_PATH: &str = "assets/galaxy_settings.json";
+const DENSITY_WAVE_PATH: &str = "assets/density_wave.json";
+
+/// Settings for the commands without a window. Like in the viewer, bad fields
+/// fall back to defaults and the problems are printed.
+fn load_settings(args: &SettingsArgs) -> (GalaxySettings, DensityWave) {
+    let overrides = args.overrides();
+
+    let path = args
+        .settings
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(GALAXY_SETTINGS_PATH));
+    let (mut galaxy_settings, problems) = match fs::read_to_string(&path) {
+        Ok(json) => validation::galaxy_settings_from_str(&json),
+        Err(err) => (GalaxySettings::default(), vec![read_problem(err)]),
+    };
+    print_problems(&path, &problems);
+    print_problems(
+        Path::new("command line"),
+        &overrides.apply_to_galaxy(&mut galaxy_settings),
+    );
+
+    let path = args
+        .density_wave
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DENSITY_WAVE_PATH));
+    let (mut density_wave, problems) = match fs::read_to_string(&path) {
+        Ok(json) => validation::density_wave_from_str(&json, &galaxy_settings),
+        Err(err) => {
+            let mut density_wave = DensityWave::for_galaxy(&galaxy_settings);
+            density_wave.build();
+            (density_wave, vec![read_problem(err)])
+        }
+    };
+    print_problems(&path, &problems);
+    print_problems(
+        Path::new("command line"),
+        &overrides.apply_to_wave(&mut density_wave, &galaxy_settings),
+    );
+
+    (galaxy_settings, density_wave)
+}
+
+/// Objects from the snapshot if one is given, generated from the settings otherwise
+pub fn load_galaxy(args: &SettingsArgs) -> Result<Snapshot, String> {
+    if let Some(path) = &args.snapshot {
+        return load_snapshot(path);
+    }
+
+    let (galaxy_settings, density_wave) = load_settings(args);
+    let objects = generator::generate(&galaxy_settings, &density_wave);
+    Ok(Snapshot {
+        galaxy_settings,
+        density_wave,
+        years: 0.,
+        objects,
+    })
+}
+
+pub fn load_snapshot(path: &Path) -> Result<Snapshot, String> {
+    Snapshot::load(path).map_err(|err| format!("unable to load {}: {}", path.display(), err))
+}
+
+fn read_problem(err: std::io::Error) -> ConfigProblem {
+    ConfigProblem::new("", format!("unable to read file ({}), using defaults", err))
+}
+
+fn print_problems(source: &Path, problems: &[ConfigProblem]) {
+    for problem in problems {
+        eprintln!("warning: {}: {}", source.display(), problem);
+    }
+}
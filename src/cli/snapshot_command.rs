@@ -0,0 +1,19 @@
+use super::settings_loader::load_galaxy;
+use super::SnapshotArgs;
+
+pub fn run(args: &SnapshotArgs) -> Result<(), String> {
+    let mut galaxy = load_galaxy(&args.settings)?;
+    if let Some(years) = args.years {
+        galaxy.years = years;
+    }
+
+    galaxy
+        .save(&args.output)
+        .map_err(|err| format!("unable to write {}: {}", args.output.display(), err))?;
+    println!(
+        "{} objects written to {}",
+        galaxy.objects.len(),
+        args.output.display()
+    );
+    Ok(())
+}
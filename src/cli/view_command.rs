@@ -0,0 +1,72 @@
+use bevy::asset::FileAssetIo;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::app_plugin::view_options_resource::ViewOptions;
+use super::settings_loader::load_snapshot;
+use super::ViewArgs;
+
+pub fn view_options(args: &ViewArgs) -> Result<ViewOptions, String> {
+    let default = ViewOptions::default();
+    let galaxy_settings_path = match &args.settings.settings {
+        Some(path) => asset_path(path)?,
+        None => default.galaxy_settings_path,
+    };
+    let density_wave_path = match &args.settings.density_wave {
+        Some(path) => asset_path(path)?,
+        None => default.density_wave_path,
+    };
+
+    let snapshot = match &args.settings.snapshot {
+        Some(path) => Some(load_snapshot(path)?),
+        None => None,
+    };
+
+    let pan_friction = match args.no_inertia {
+        true => None,
+        false => Some(positive("pan friction", args.pan_friction)?),
+    };
+    let zoom_smoothing = match args.no_smooth_zoom {
+        true => None,
+        false => Some(positive("zoom smoothing", args.zoom_smoothing)?),
+    };
+
+    Ok(ViewOptions {
+        galaxy_settings_path,
+        density_wave_path,
+        overrides: args.settings.overrides(),
+        camera_scale: args.scale,
+        bloom: args.bloom.bloom(args.scale),
+        pan_friction,
+        zoom_smoothing,
+        snapshot,
+        years: args.years,
+    })
+}
+
+fn positive(name: &str, value: f32) -> Result<f32, String> {
+    if value.is_finite() && value > 0. {
+        Ok(value)
+    } else {
+        Err(format!("{} has to be positive, got {}", name, value))
+    }
+}
+
+// The viewer hot-reloads settings through the asset server,
+// which only watches files inside the assets folder
+fn asset_path(path: &Path) -> Result<PathBuf, String> {
+    let assets = FileAssetIo::get_base_path().join("assets");
+    let assets = fs::canonicalize(&assets)
+        .map_err(|err| format!("unable to find {}: {}", assets.display(), err))?;
+    let file = fs::canonicalize(path)
+        .map_err(|err| format!("unable to read {}: {}", path.display(), err))?;
+    file.strip_prefix(&assets)
+        .map(Path::to_path_buf)
+        .map_err(|_| {
+            format!(
+                "{} has to be inside {} to be viewed",
+                path.display(),
+                assets.display()
+            )
+        })
+}
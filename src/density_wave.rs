@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+
+use super::galaxy_setting_component::GalaxySettings;
+
+/// Surface brightness profile the radii are drawn from, the probability
+/// of a radius follows the light in the ring there, `r * I(r)`. `i0` scales all of them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Profile {
+    /// de Vaucouleurs bulge out to `bulge_radius` joined to an exponential disc,
+    /// shaped by `k` and `a`. Deliberately drawn from `I(r)` without the ring area,
+    /// like the original model, so existing galaxies and presets keep their look.
+    #[default]
+    BulgeDisc,
+    Sersic {
+        n: f32,
+        effective_radius: f32,
+    },
+    Exponential {
+        scale_length: f32,
+    },
+    /// Star clusters and dwarf spheroidals, nothing beyond the tidal radius
+    King {
+        core_radius: f32,
+        tidal_radius: f32,
+    },
+    /// Disc with a break, steeper outside for truncated and shallower for antitruncated discs
+    DoubleExponential {
+        inner_scale_length: f32,
+        outer_scale_length: f32,
+        break_radius: f32,
+    },
+}
+
+/// b_n of a Sérsic profile of index `n`, so that half of the light is inside the
+/// effective radius. Approximation of Ciotti & Bertin.
+pub fn sersic_b(n: f32) -> f32 {
+    2. * n - 1. / 3. + 4. / (405. * n)
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DensityWave {
+    pub min: f32,
+    pub max: f32,
+    pub steps: i32,
+    pub i0: f32,
+    pub k: f32,
+    pub a: f32,
+    pub bulge_radius: f32,
+    pub profile: Profile,
+
+    #[serde(skip)]
+    pub m1: Vec<f32>,
+    #[serde(skip)]
+    pub y1: Vec<f32>,
+    #[serde(skip)]
+    pub x1: Vec<f32>,
+
+    #[serde(skip)]
+    pub m2: Vec<f32>,
+    #[serde(skip)]
+    pub y2: Vec<f32>,
+    #[serde(skip)]
+    pub x2: Vec<f32>,
+}
+
+impl Default for DensityWave {
+    /// Wave matching the default `GalaxySettings`, not built yet
+    fn default() -> Self {
+        DensityWave::for_galaxy(&GalaxySettings::default())
+    }
+}
+
+impl DensityWave {
+    /// Wave scaled to the radii of the galaxy, not built yet
+    pub fn for_galaxy(settings: &GalaxySettings) -> Self {
+        DensityWave {
+            min: 0.,
+            max: settings.radius * 2.,
+            steps: 1000,
+            i0: 1.,
+            k: 0.02,
+            a: settings.radius / 3.,
+            bulge_radius: settings.bulge_radius,
+            profile: Profile::BulgeDisc,
+            m1: vec![],
+            y1: vec![],
+            x1: vec![],
+            m2: vec![],
+            y2: vec![],
+            x2: vec![],
+        }
+    }
+
+    pub fn val_from_prob(&self, val: f32) -> f32 {
+        let h = 1.0 / (self.y2.len() - 1) as f32;
+        let i = (val / h).floor();
+        let remainder = val - i * h;
+
+        self.y2[i as usize] + self.m2[i as usize] * remainder
+    }
+
+    pub fn build(&mut self) {
+        let mut h = (self.max - self.min) / self.steps as f32;
+        let mut y = 0.;
+
+        self.x1.clear();
+        self.y1.clear();
+        self.x2.clear();
+        self.y2.clear();
+        self.m1.clear();
+        self.m2.clear();
+
+        // Simpson rule for integration of the distribution function
+        self.y1.push(0.0);
+        self.x1.push(0.0);
+        for i in (0..self.steps).step_by(2) {
+            let x = h * (i + 2) as f32;
+            y += h / 3.
+                * (self.ring_light(self.min + i as f32 * h)
+                    + 4. * self.ring_light(self.min + (i + 1) as f32 * h)
+                    + self.ring_light(self.min + (i + 2) as f32 * h));
+
+            self.m1.push((y - self.y1.last().unwrap()) / (2. * h));
+            self.x1.push(x);
+            self.y1.push(y);
+        }
+        self.m1.push(0.0);
+
+        // normieren
+        for i in 0..self.y1.len() {
+            let y1_last = self.y1.last().cloned().unwrap();
+            self.y1[i] /= y1_last;
+            self.m1[i] /= y1_last;
+        }
+
+        self.x2.push(0.0);
+        self.y2.push(0.0);
+
+        h = 1. / self.steps as f32;
+        let mut k = 0;
+        for i in 1..self.steps {
+            let p = i as f32 * h;
+
+            while self.y1[k + 1] <= p {
+                k += 1;
+            }
+
+            y = self.x1[k] + (p - self.y1[k]) / self.m1[k];
+
+            self.m2.push((y - self.y2.last().unwrap()) / h);
+            self.x2.push(p);
+            self.y2.push(y);
+        }
+        self.m2.push(0.0);
+    }
+
+    /// Light of the ring at radius `x`, what the distribution integrates
+    fn ring_light(&self, x: f32) -> f32 {
+        match self.profile {
+            Profile::BulgeDisc => self.intensity(x),
+            _ => x * self.intensity(x),
+        }
+    }
+
+    fn intensity(&self, x: f32) -> f32 {
+        match self.profile {
+            Profile::BulgeDisc => self.intensity_bulge_disc(x),
+            Profile::Sersic {
+                n,
+                effective_radius,
+            } => self.i0 * (-sersic_b(n) * (x / effective_radius).powf(1. / n)).exp(),
+            Profile::Exponential { scale_length } => self.intensity_disc(x, self.i0, scale_length),
+            Profile::King {
+                core_radius,
+                tidal_radius,
+            } => {
+                if x >= tidal_radius {
+                    return 0.;
+                }
+                let term = |r: f32| 1. / (1. + (r / core_radius).powi(2)).sqrt();
+                let edge = term(tidal_radius);
+                self.i0 * ((term(x) - edge) / (1. - edge)).powi(2)
+            }
+            Profile::DoubleExponential {
+                inner_scale_length,
+                outer_scale_length,
+                break_radius,
+            } => {
+                if x < break_radius {
+                    self.intensity_disc(x, self.i0, inner_scale_length)
+                } else {
+                    // Continuous at the break
+                    let i0 = self.intensity_disc(break_radius, self.i0, inner_scale_length);
+                    self.intensity_disc(x - break_radius, i0, outer_scale_length)
+                }
+            }
+        }
+    }
+
+    fn intensity_bulge_disc(&self, x: f32) -> f32 {
+        if x < self.bulge_radius {
+            self.intensity_bulge(x, self.i0, self.k)
+        } else {
+            let i0 = self.intensity_bulge(self.bulge_radius, self.i0, self.k);
+            self.intensity_disc(x - self.bulge_radius, i0, self.a)
+        }
+    }
+
+    fn intensity_bulge(&self, r: f32, i0: f32, k: f32) -> f32 {
+        i0 * (-k * r.powf(0.25)).exp()
+    }
+
+    fn intensity_disc(&self, r: f32, i0: f32, a: f32) -> f32 {
+        i0 * (-r / a).exp()
+    }
+}
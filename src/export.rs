@@ -0,0 +1,166 @@
+//! Catalog of the generated objects for other tools, as CSV or JSON Lines.
+
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use super::appearance;
+use super::galaxy_setting_component::GalaxySettings;
+use super::generator::GalaxyObject;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 2] = [ExportFormat::Csv, ExportFormat::Jsonl];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+
+    /// Format matching the extension of the file, if any
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        extension.parse().ok()
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" | "ndjson" => Ok(ExportFormat::Jsonl),
+            _ => Err(format!("unknown format {}, expected csv or jsonl", text)),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// One exported object: orbit parameters, position at the epoch and sprite color.
+/// The color is sRGB like the sprite, components above 1 are the part that glows.
+#[derive(Serialize)]
+pub struct CatalogRecord {
+    pub kind: &'static str,
+    /// Turns with the bar instead of orbiting on its own
+    pub is_bar: bool,
+    pub a: f32,
+    pub b: f32,
+    pub theta0: f32,
+    pub vel_theta: f32,
+    pub tilt_angle: f32,
+    pub temp: f32,
+    pub mag: f32,
+    pub x: f32,
+    pub y: f32,
+    pub color_r: f32,
+    pub color_g: f32,
+    pub color_b: f32,
+    pub color_a: f32,
+}
+
+impl CatalogRecord {
+    const CSV_HEADER: &'static str =
+        "kind,is_bar,a,b,theta0,vel_theta,tilt_angle,temp,mag,x,y,color_r,color_g,color_b,color_a";
+
+    pub fn new(object: &GalaxyObject, settings: &GalaxySettings, years: f64) -> Self {
+        let star = &object.star;
+        let position = star.position(settings, years);
+        let color = appearance::object_color(object.kind, star);
+        CatalogRecord {
+            kind: object.kind.name(),
+            is_bar: star.is_bar,
+            a: star.a,
+            b: star.b,
+            theta0: star.theta0,
+            vel_theta: star.vel_theta,
+            tilt_angle: star.tilt_angle,
+            temp: star.temp,
+            mag: star.mag,
+            x: position.x,
+            y: position.y,
+            color_r: color.r,
+            color_g: color.g,
+            color_b: color.b,
+            color_a: color.a,
+        }
+    }
+
+    fn write_csv_row(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.kind,
+            self.is_bar,
+            self.a,
+            self.b,
+            self.theta0,
+            self.vel_theta,
+            self.tilt_angle,
+            self.temp,
+            self.mag,
+            self.x,
+            self.y,
+            self.color_r,
+            self.color_g,
+            self.color_b,
+            self.color_a
+        )
+    }
+}
+
+/// Write every object in the format, positions are taken at `years`
+pub fn write(
+    writer: &mut impl Write,
+    format: ExportFormat,
+    objects: &[GalaxyObject],
+    settings: &GalaxySettings,
+    years: f64,
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Csv => write_csv(writer, objects, settings, years),
+        ExportFormat::Jsonl => write_jsonl(writer, objects, settings, years),
+    }
+}
+
+/// Write a header and one CSV row per object
+pub fn write_csv(
+    writer: &mut impl Write,
+    objects: &[GalaxyObject],
+    settings: &GalaxySettings,
+    years: f64,
+) -> io::Result<()> {
+    writeln!(writer, "{}", CatalogRecord::CSV_HEADER)?;
+    for object in objects {
+        CatalogRecord::new(object, settings, years).write_csv_row(writer)?;
+    }
+    Ok(())
+}
+
+/// Write one json object per line
+pub fn write_jsonl(
+    writer: &mut impl Write,
+    objects: &[GalaxyObject],
+    settings: &GalaxySettings,
+    years: f64,
+) -> io::Result<()> {
+    for object in objects {
+        serde_json::to_writer(&mut *writer, &CatalogRecord::new(object, settings, years))?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Numbered PNG frames at a fixed timestep, for video.
+//!
+//! Frame `n` is always `n / fps` seconds after the start, however long it took
+//! to draw, so the same galaxy and camera give the same sequence every time.
+
+use std::path::{Path, PathBuf};
+
+pub const FRAMES_DIR: &str = "frames";
+
+/// Simulated time of each frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameClock {
+    pub fps: f32,
+    /// Epoch of the first frame
+    pub start_years: f64,
+    /// Simulated years per second of video
+    pub years_per_second: f64,
+}
+
+impl FrameClock {
+    /// Seconds of video between two frames
+    pub fn step(&self) -> f32 {
+        1. / self.fps
+    }
+
+    /// Seconds of video from the first frame
+    pub fn seconds(&self, frame: u32) -> f32 {
+        (frame as f64 / self.fps as f64) as f32
+    }
+
+    pub fn years(&self, frame: u32) -> f64 {
+        self.start_years + frame as f64 / self.fps as f64 * self.years_per_second
+    }
+
+    /// Frames to cover `seconds`, both ends included
+    pub fn frame_count(&self, seconds: f32) -> u32 {
+        (seconds.max(0.) * self.fps).ceil() as u32 + 1
+    }
+}
+
+/// `frame_000042.png` in the directory, numbered for video encoders
+pub fn frame_path(dir: &Path, frame: u32) -> PathBuf {
+    dir.join(format!("frame_{:06}.png", frame))
+}
+
+/// First directory of the form `take_001` that does not exist yet
+pub fn next_free_take(dir: &Path) -> PathBuf {
+    (1..)
+        .map(|i| dir.join(format!("take_{:03}", i)))
+        .find(|take| !take.exists())
+        .unwrap()
+}
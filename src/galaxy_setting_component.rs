@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+use std::{f32, f64};
+
+use super::star::ObjectKind;
+
+const PC_TO_KM: f32 = 3.08567758129e13;
+const SEC_PER_YEAR: f32 = 365.25 * 86400.;
+const CONSTANT_OF_GRAVITY: f32 = 6.672e-11;
+
+/// Family of galaxies the generator builds
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Morphology {
+    #[default]
+    Spiral,
+    /// E0 to E7, a smooth spheroid of old stars without gas or dust
+    Elliptical,
+    /// S0, a disc and bulge without arms
+    Lenticular,
+}
+
+impl Morphology {
+    pub const ALL: [Morphology; 3] = [
+        Morphology::Spiral,
+        Morphology::Elliptical,
+        Morphology::Lenticular,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Morphology::Spiral => "spiral",
+            Morphology::Elliptical => "elliptical",
+            Morphology::Lenticular => "lenticular",
+        }
+    }
+
+    /// Only spirals have dust lanes and star forming regions
+    pub fn has_kind(&self, kind: ObjectKind) -> bool {
+        match self {
+            Morphology::Spiral => true,
+            Morphology::Elliptical => kind == ObjectKind::Star,
+            Morphology::Lenticular => matches!(kind, ObjectKind::Star | ObjectKind::Dust),
+        }
+    }
+}
+
+/// How the arms of spirals are made
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArmModel {
+    /// Stars crowd where the tilted ellipses of their orbits come close
+    #[default]
+    DensityWave,
+    /// Stars start on logarithmic spirals and wind up with the rotation
+    LogSpiral,
+}
+
+impl ArmModel {
+    pub const ALL: [ArmModel; 2] = [ArmModel::DensityWave, ArmModel::LogSpiral];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArmModel::DensityWave => "density wave",
+            ArmModel::LogSpiral => "log spiral",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct GalaxySettings {
+    pub radius: f32,
+    pub bulge_radius: f32,
+    pub far_field_radius: f32,
+    pub angular_offset: f32,
+    pub inner_excentricity: f32,
+    pub outter_excentricity: f32,
+    pub ellipse_disturbances: i32,
+    pub ellipse_disturbances_damping: i32,
+    pub count_stars: usize,
+    pub count_dusts: usize,
+    pub count_dusts_filaments: usize,
+    pub count_h2: usize,
+    pub count_h2_core: usize,
+    pub has_dark_matter: bool,
+    pub base_temp: f32,
+    pub dust_render_size: f32,
+    pub pert_n: i32,
+    pub pert_amp: i32,
+    pub seed: u64,
+    /// Distance from the centre to the ends of the bar, 0 for no bar
+    pub bar_length: f32,
+    /// Minor to major axis of the bar orbits
+    pub bar_axis_ratio: f32,
+    /// Rotation of the bar in km/s/kpc. The arms don't turn with it.
+    pub bar_pattern_speed: f32,
+    /// Portion of the stars on bar orbits
+    pub bar_star_fraction: f32,
+    pub morphology: Morphology,
+    /// n of the En class of ellipticals, the axis ratio is 1 - n/10
+    pub elliptical_class: u8,
+    /// Sérsic index of the light profile of ellipticals, 4 is de Vaucouleurs
+    pub sersic_index: f32,
+    pub arm_model: ArmModel,
+    /// Number of logarithmic arms
+    pub arm_count: u8,
+    /// Angle between a logarithmic arm and the circle at its radius, in degrees
+    pub arm_pitch: f32,
+    /// Scatter of the stars across a logarithmic arm
+    pub arm_width: f32,
+}
+
+impl Default for GalaxySettings {
+    fn default() -> Self {
+        GalaxySettings {
+            radius: 13000.,
+            far_field_radius: 16000. * 2.,
+            bulge_radius: 4000.,
+            angular_offset: 0.0004,
+            inner_excentricity: 0.85,
+            outter_excentricity: 0.95,
+            ellipse_disturbances: 0,
+            ellipse_disturbances_damping: 40,
+            count_stars: 40000,
+            count_dusts: 40000,
+            count_dusts_filaments: 40000,
+            count_h2: 400,
+            count_h2_core: 400,
+            has_dark_matter: true,
+            base_temp: 4000.,
+            dust_render_size: 70.,
+            seed: 1234567890,
+            pert_n: 2,
+            pert_amp: 40,
+            bar_length: 0.,
+            bar_axis_ratio: 0.3,
+            bar_pattern_speed: 30.,
+            bar_star_fraction: 0.25,
+            morphology: Morphology::Spiral,
+            elliptical_class: 3,
+            sersic_index: 4.,
+            arm_model: ArmModel::DensityWave,
+            arm_count: 2,
+            arm_pitch: 12.,
+            arm_width: 600.,
+        }
+    }
+}
+
+impl GalaxySettings {
+    pub fn get_count_all_objects(&self) -> usize {
+        self.count_stars
+            + self.count_dusts
+            + self.count_dusts_filaments
+            + self.count_h2
+            + self.count_h2_core
+    }
+
+    pub fn get_count(&self, kind: ObjectKind) -> usize {
+        match kind {
+            ObjectKind::Star => self.count_stars,
+            ObjectKind::Dust => self.count_dusts,
+            ObjectKind::DustFilament => self.count_dusts_filaments,
+            ObjectKind::H2 => self.count_h2,
+            ObjectKind::H2Core => self.count_h2_core,
+        }
+    }
+
+    pub fn get_count_mut(&mut self, kind: ObjectKind) -> &mut usize {
+        match kind {
+            ObjectKind::Star => &mut self.count_stars,
+            ObjectKind::Dust => &mut self.count_dusts,
+            ObjectKind::DustFilament => &mut self.count_dusts_filaments,
+            ObjectKind::H2 => &mut self.count_h2,
+            ObjectKind::H2Core => &mut self.count_h2_core,
+        }
+    }
+
+    /// True if both settings only differ in the object counts
+    pub fn differs_only_in_counts(&self, other: &GalaxySettings) -> bool {
+        let mut same_counts = other.clone();
+        for kind in ObjectKind::ALL {
+            *same_counts.get_count_mut(kind) = self.get_count(kind);
+        }
+        *self == same_counts
+    }
+
+    pub fn get_excentricity(&self, rad: f32) -> f32 {
+        if rad < self.bulge_radius {
+            // Core region of the galaxy. Innermost part is round
+            // excentricity increasing linear to the border of the core.
+            1. + (rad / self.bulge_radius) * (self.inner_excentricity - 1.)
+        } else if rad > self.bulge_radius && rad <= self.radius {
+            self.inner_excentricity
+                + (rad - self.bulge_radius) / (self.radius - self.bulge_radius)
+                    * (self.outter_excentricity - self.inner_excentricity)
+        } else if rad > self.radius && rad < self.far_field_radius {
+            // excentricity is slowly reduced to 1.
+            self.outter_excentricity
+                + (rad - self.radius) / (self.far_field_radius - self.radius)
+                    * (1. - self.outter_excentricity)
+        } else {
+            1.
+        }
+    }
+
+    pub fn get_angular_offset(&self, rad: f32) -> f32 {
+        if self.has_bar() {
+            // Orbits inside the bar are aligned with it, the arms wind from its ends
+            rad.max(self.bar_length) * self.angular_offset
+        } else {
+            rad * self.angular_offset
+        }
+    }
+
+    /// Disc orbits are disturbed, ellipticals stay smooth
+    pub fn has_ellipse_disturbances(&self) -> bool {
+        self.ellipse_disturbances > 0 && self.morphology != Morphology::Elliptical
+    }
+
+    /// Scale of the size and change of the tilt of the ellipse at the radius.
+    /// `ellipse_disturbances` waves run out to the far field radius,
+    /// `ellipse_disturbances_damping` divides their amplitude.
+    pub fn get_ellipse_disturbance(&self, rad: f32) -> (f32, f32) {
+        if !self.has_ellipse_disturbances() {
+            return (1., 0.);
+        }
+        let phase =
+            self.ellipse_disturbances as f32 * f32::consts::TAU * rad / self.far_field_radius;
+        let strength = 1. / self.ellipse_disturbances_damping as f32;
+        (
+            1. + strength * phase.sin(),
+            f32::consts::PI * strength * phase.cos(),
+        )
+    }
+
+    /// Ellipticals have no bar, lenticulars can
+    pub fn has_bar(&self) -> bool {
+        self.bar_length > 0. && self.morphology != Morphology::Elliptical
+    }
+
+    pub fn has_arms(&self) -> bool {
+        self.morphology == Morphology::Spiral
+    }
+
+    pub fn has_log_spiral_arms(&self) -> bool {
+        self.has_arms() && self.arm_model == ArmModel::LogSpiral
+    }
+
+    /// Logarithmic arms start at the ends of the bar or else at the bulge
+    pub fn get_arm_start_radius(&self) -> f32 {
+        let start = if self.has_bar() {
+            self.bar_length
+        } else {
+            self.bulge_radius
+        };
+        start.max(1.)
+    }
+
+    /// Direction in radians of the centre of logarithmic arm `arm` at the radius at epoch 0.
+    /// Arm stars orbit on their own afterwards, so with a turning bar the arms drift
+    /// away from its ends.
+    pub fn get_arm_angle(&self, arm: u8, rad: f32) -> f32 {
+        // The bar axis is at minus its tilt, the arms trail behind the rotation
+        let start = if self.has_bar() {
+            -self.get_bar_angle()
+        } else {
+            0.
+        };
+        let spacing = f32::consts::TAU / self.arm_count as f32;
+        let winding = (rad / self.get_arm_start_radius()).ln() / self.arm_pitch.to_radians().tan();
+        start + arm as f32 * spacing - winding
+    }
+
+    /// Minor to major axis of the ellipticals
+    pub fn get_elliptical_axis_ratio(&self) -> f32 {
+        1. - self.elliptical_class as f32 / 10.
+    }
+
+    /// Half of the light of an elliptical is inside this radius
+    pub fn get_effective_radius(&self) -> f32 {
+        self.radius / 4.
+    }
+
+    /// Tilt of the bar orbits at epoch 0, the one of the ellipses at the bar ends
+    pub fn get_bar_angle(&self) -> f32 {
+        self.bar_length * self.angular_offset
+    }
+
+    /// Rotation of the bar at the epoch in radians, none without a bar.
+    /// Only bar orbits turn by it, see `get_arm_angle`.
+    pub fn get_pattern_angle(&self, years: f64) -> f32 {
+        if !self.has_bar() {
+            return 0.;
+        }
+        // km/s/kpc to degrees per year
+        let deg_per_year =
+            self.bar_pattern_speed as f64 * SEC_PER_YEAR as f64 / (1000. * PC_TO_KM as f64) * 180.
+                / f64::consts::PI;
+        ((deg_per_year * years) % 360.).to_radians() as f32
+    }
+
+    pub fn get_orbital_velocity(&self, rad: f32) -> f32 {
+        let vel_kms: f32; // velovity in kilometer per seconds
+        if self.has_dark_matter {
+            vel_kms = GalaxySettings::velocity_with_dark_matter(rad);
+        } else {
+            vel_kms = GalaxySettings::velocity_without_dark_matter(rad);
+        }
+
+        // Calculate velocity in degree per year
+        let u = 2.0 * f32::consts::PI * rad * PC_TO_KM;
+        let time = u / (vel_kms * SEC_PER_YEAR);
+
+        360.0 / time
+    }
+
+    /// Years for one orbit at the radius
+    pub fn get_orbital_period(&self, rad: f32) -> f64 {
+        360. / self.get_orbital_velocity(rad) as f64
+    }
+
+    fn velocity_with_dark_matter(r: f32) -> f32 {
+        const MZ: f32 = 100.;
+        let mass_halo = GalaxySettings::mass_halo(r);
+        let mass_disc = GalaxySettings::mass_disc(r);
+        let v = 20000.0 * (CONSTANT_OF_GRAVITY * (mass_halo + mass_disc + MZ) / r).sqrt();
+        return v;
+    }
+
+    fn velocity_without_dark_matter(r: f32) -> f32 {
+        const MZ: f32 = 100.;
+        20000.0 * (CONSTANT_OF_GRAVITY * (GalaxySettings::mass_disc(r) + MZ) / r).sqrt()
+    }
+
+    fn mass_disc(r: f32) -> f32 {
+        let d = 2000.; // thickness of the disc
+        let rho_so = 1.; // density at the center
+        let r_h = 2000.; // radius at which the density has fallen by half
+        return rho_so * (-r / r_h).exp() * (r * r) * f32::consts::PI * d;
+    }
+
+    fn mass_halo(r: f32) -> f32 {
+        let rho_h0 = 0.15;
+        let r_c = 2500.;
+        return rho_h0 * 1. / (1. + (r / r_c).powf(2.)) * (4. * f32::consts::PI * r.powf(3.) / 3.);
+    }
+}
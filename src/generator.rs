@@ -0,0 +1,510 @@
+use rand::prelude::*;
+use std::f32;
+use std::ops::Range;
+
+use super::density_wave::{sersic_b, DensityWave};
+use super::galaxy_setting_component::{GalaxySettings, Morphology};
+use super::star::{ObjectKind, Star};
+
+/// A generated object of the galaxy together with its kind
+#[derive(Debug, Clone)]
+pub struct GalaxyObject {
+    pub kind: ObjectKind,
+    pub star: Star,
+}
+
+/// Generate all objects with the seed from the settings
+pub fn generate(galaxy_setting: &GalaxySettings, density_wave: &DensityWave) -> Vec<GalaxyObject> {
+    generate_with_seed(galaxy_setting, density_wave, galaxy_setting.seed)
+}
+
+/// Generate all objects in the same order the application spawns them.
+/// `density_wave` must be built.
+pub fn generate_with_seed(
+    galaxy_setting: &GalaxySettings,
+    density_wave: &DensityWave,
+    seed: u64,
+) -> Vec<GalaxyObject> {
+    let mut objects = Vec::with_capacity(galaxy_setting.get_count_all_objects());
+    for kind in ObjectKind::ALL {
+        let stars = create_objects(kind, galaxy_setting, density_wave, seed);
+        objects.extend(stars.into_iter().map(|star| GalaxyObject { kind, star }));
+    }
+    objects
+}
+
+/// Generate the objects of one kind from its own random stream.
+///
+/// The result for a smaller count is a prefix of the result for a larger one,
+/// except for the objects that became brighter or dimmer with `bright_count`.
+pub fn create_objects(
+    kind: ObjectKind,
+    galaxy_setting: &GalaxySettings,
+    density_wave: &DensityWave,
+    seed: u64,
+) -> Vec<Star> {
+    create_object_range(kind, galaxy_setting, density_wave, seed, 0..usize::MAX)
+}
+
+/// The objects of `create_objects` at the indices in `range`, without drawing
+/// the ones before. Every object has a random stream of its own.
+pub fn create_object_range(
+    kind: ObjectKind,
+    galaxy_setting: &GalaxySettings,
+    density_wave: &DensityWave,
+    seed: u64,
+    range: Range<usize>,
+) -> Vec<Star> {
+    if !galaxy_setting.morphology.has_kind(kind) {
+        return vec![];
+    }
+    let stream_seed = kind.stream_seed(seed);
+    match kind {
+        ObjectKind::Star => create_stars(galaxy_setting, density_wave, stream_seed, range),
+        ObjectKind::Dust => create_dusts(galaxy_setting, density_wave, stream_seed, range),
+        ObjectKind::DustFilament => create_dusts_filaments(galaxy_setting, stream_seed, range),
+        ObjectKind::H2 => create_h2(galaxy_setting, stream_seed, range),
+        ObjectKind::H2Core => create_h2_core(galaxy_setting, stream_seed, range),
+    }
+}
+
+/// Number of objects `create_objects` makes of the kind
+pub fn object_count(kind: ObjectKind, galaxy_setting: &GalaxySettings, seed: u64) -> usize {
+    if !galaxy_setting.morphology.has_kind(kind) {
+        return 0;
+    }
+    match kind {
+        // The black hole is always there
+        ObjectKind::Star => galaxy_setting.count_stars.max(1),
+        ObjectKind::Dust => galaxy_setting.count_dusts,
+        ObjectKind::DustFilament => {
+            let stream_seed = kind.stream_seed(seed);
+            (0..galaxy_setting.count_dusts_filaments / FILAMENT_FACTOR)
+                .map(|filament| filament_len(&mut object_rng(stream_seed, filament)))
+                .sum()
+        }
+        ObjectKind::H2 => galaxy_setting.count_h2,
+        ObjectKind::H2Core => galaxy_setting.count_h2_core,
+    }
+}
+
+/// Objects of the kind below this index are made brighter.
+/// Stars and dust both follow the star count.
+pub fn bright_count(kind: ObjectKind, galaxy_setting: &GalaxySettings) -> usize {
+    match kind {
+        ObjectKind::Star | ObjectKind::Dust => galaxy_setting.count_stars / 60,
+        _ => 0,
+    }
+}
+
+/// Random stream of the object at `index`, or of the filament for filaments
+fn object_rng(stream_seed: u64, index: usize) -> StdRng {
+    const SALT: u64 = 0xD1B5_4A32_D192_ED03;
+    StdRng::seed_from_u64(stream_seed ^ SALT.wrapping_mul(index as u64 + 1))
+}
+
+/// Swell and turn the ellipse of a density wave disc orbit by the disturbance
+/// at its radius. Only depends on the ellipse, so the stream stays prefix stable.
+fn disturb_ellipse(galaxy_setting: &GalaxySettings, star: &mut Star) {
+    if !galaxy_setting.has_ellipse_disturbances() {
+        return;
+    }
+    let (scale, tilt) = galaxy_setting.get_ellipse_disturbance(star.a);
+    star.a *= scale;
+    star.b *= scale;
+    star.tilt_angle += tilt;
+}
+
+pub fn create_h2(
+    galaxy_setting: &GalaxySettings,
+    stream_seed: u64,
+    range: Range<usize>,
+) -> Vec<Star> {
+    (range.start..range.end.min(galaxy_setting.count_h2))
+        .map(|i| create_h2_region(galaxy_setting, &mut object_rng(stream_seed, i)))
+        .collect()
+}
+
+pub fn create_h2_core(
+    galaxy_setting: &GalaxySettings,
+    stream_seed: u64,
+    range: Range<usize>,
+) -> Vec<Star> {
+    (range.start..range.end.min(galaxy_setting.count_h2_core))
+        .map(|i| create_h2_region(galaxy_setting, &mut object_rng(stream_seed, i)))
+        .collect()
+}
+
+fn create_h2_region(galaxy_setting: &GalaxySettings, rnd: &mut StdRng) -> Star {
+    let x: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
+    let y: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
+    let rad = f32::sqrt(x * x + y * y);
+
+    let temp = 6000. + (6000. * rnd.gen::<f32>() - 3000.);
+    let mag = 0.1 + 0.05 * rnd.gen::<f32>();
+    if is_on_log_spiral_arm(galaxy_setting, rad) {
+        return create_arm_star(galaxy_setting, rad, temp, mag, rnd);
+    }
+    let b = rad * galaxy_setting.get_excentricity(rad);
+    Star {
+        theta0: 360.0 * rnd.gen::<f32>(),
+        vel_theta: galaxy_setting.get_orbital_velocity((rad + b) / 2.),
+        tilt_angle: galaxy_setting.get_angular_offset(rad),
+        a: rad,
+        b,
+        temp,
+        mag,
+        is_bar: false,
+    }
+}
+
+pub fn create_stars(
+    galaxy_setting: &GalaxySettings,
+    density_wave: &DensityWave,
+    stream_seed: u64,
+    range: Range<usize>,
+) -> Vec<Star> {
+    let bright_count = bright_count(ObjectKind::Star, galaxy_setting);
+    (range.start..range.end.min(galaxy_setting.count_stars.max(1)))
+        .map(|i| {
+            // First star ist the black hole at the centre
+            if i == 0 {
+                return Star {
+                    theta0: 0.,
+                    vel_theta: 0.,
+                    tilt_angle: 0.,
+                    a: 0.,
+                    b: 0.,
+                    temp: 6000.,
+                    mag: 1.0,
+                    is_bar: false,
+                };
+            }
+            let mut rnd = object_rng(stream_seed, i);
+            create_star(galaxy_setting, density_wave, i < bright_count, &mut rnd)
+        })
+        .collect()
+}
+
+fn create_star(
+    galaxy_setting: &GalaxySettings,
+    density_wave: &DensityWave,
+    is_bright: bool,
+    rnd: &mut StdRng,
+) -> Star {
+    if galaxy_setting.morphology == Morphology::Elliptical {
+        return create_elliptical_star(galaxy_setting, rnd);
+    }
+
+    // Only drawn with a bar, so galaxies without one stay the same
+    if galaxy_setting.has_bar() && rnd.gen::<f32>() < galaxy_setting.bar_star_fraction {
+        return create_bar_star(galaxy_setting, rnd);
+    }
+
+    let rad: f32 = density_wave.val_from_prob(rnd.gen());
+    let temp = if galaxy_setting.morphology == Morphology::Lenticular {
+        // No star formation left in the disc, the hot young stars are gone
+        (3000. * rnd.gen::<f32>() - 1500.) + 5000.
+    } else {
+        (4000. * rnd.gen::<f32>() - 2000.) + 6000.
+    };
+    let mag = 0.1 + 0.4 * rnd.gen::<f32>();
+    let mut star = if is_on_log_spiral_arm(galaxy_setting, rad) {
+        create_arm_star(galaxy_setting, rad, temp, mag, rnd)
+    } else {
+        let mut star = Star {
+            theta0: 360.0 * rnd.gen::<f32>(),
+            vel_theta: galaxy_setting.get_orbital_velocity(rad),
+            tilt_angle: get_disc_tilt(galaxy_setting, rad, rnd),
+            a: rad,
+            b: rad * galaxy_setting.get_excentricity(rad),
+            temp,
+            mag,
+            is_bar: false,
+        };
+        disturb_ellipse(galaxy_setting, &mut star);
+        star
+    };
+
+    // Make a small portion of the stars brighter
+    if is_bright {
+        star.mag = 1_f32.min(star.mag + 0.1 + rnd.gen::<f32>() * 0.4);
+    }
+    star
+}
+
+/// Tilt of a disc orbit. Spirals wind their orbits into arms,
+/// lenticulars spread them evenly into a smooth disc.
+fn get_disc_tilt(galaxy_setting: &GalaxySettings, rad: f32, rnd: &mut StdRng) -> f32 {
+    if galaxy_setting.has_arms() {
+        galaxy_setting.get_angular_offset(rad)
+    } else {
+        f32::consts::TAU * rnd.gen::<f32>()
+    }
+}
+
+/// Logarithmic arms leave the bulge and the bar as they are
+fn is_on_log_spiral_arm(galaxy_setting: &GalaxySettings, rad: f32) -> bool {
+    galaxy_setting.has_log_spiral_arms() && rad > galaxy_setting.get_arm_start_radius()
+}
+
+/// Object on a circular orbit through one of the logarithmic arms, scattered across it
+fn create_arm_star(
+    galaxy_setting: &GalaxySettings,
+    rad: f32,
+    temp: f32,
+    mag: f32,
+    rnd: &mut StdRng,
+) -> Star {
+    let (arm, offset) = draw_arm_offset(galaxy_setting, rnd);
+    arm_orbit(galaxy_setting, arm, offset, rad, temp, mag)
+}
+
+/// Random arm and distance across it
+fn draw_arm_offset(galaxy_setting: &GalaxySettings, rnd: &mut StdRng) -> (u8, f32) {
+    let arm = rnd.gen_range(0..galaxy_setting.arm_count);
+    (arm, normal(rnd) * galaxy_setting.arm_width)
+}
+
+fn arm_orbit(
+    galaxy_setting: &GalaxySettings,
+    arm: u8,
+    offset: f32,
+    rad: f32,
+    temp: f32,
+    mag: f32,
+) -> Star {
+    let theta = galaxy_setting.get_arm_angle(arm, rad) + offset / rad;
+    Star {
+        theta0: theta.to_degrees().rem_euclid(360.),
+        vel_theta: galaxy_setting.get_orbital_velocity(rad),
+        tilt_angle: 0.,
+        a: rad,
+        b: rad,
+        temp,
+        mag,
+        is_bar: false,
+    }
+}
+
+/// Old star of an elliptical, all orbits share the shape of the isophotes
+fn create_elliptical_star(galaxy_setting: &GalaxySettings, rnd: &mut StdRng) -> Star {
+    let a = loop {
+        let rad = sersic_radius(
+            galaxy_setting.sersic_index,
+            galaxy_setting.get_effective_radius(),
+            rnd,
+        );
+        if rad <= galaxy_setting.far_field_radius {
+            break rad;
+        }
+    };
+    let b = a * galaxy_setting.get_elliptical_axis_ratio();
+    // Orbits go either way, so the galaxy as a whole hardly rotates
+    let direction = if rnd.gen::<bool>() { 1. } else { -1. };
+    let temp = (2000. * rnd.gen::<f32>() - 1000.) + 4000.;
+    let mag = 0.1 + 0.4 * rnd.gen::<f32>();
+    Star {
+        theta0: 360.0 * rnd.gen::<f32>(),
+        vel_theta: direction * galaxy_setting.get_orbital_velocity(((a + b) / 2.).max(1.)),
+        tilt_angle: 0.,
+        a,
+        b,
+        temp,
+        mag,
+        is_bar: false,
+    }
+}
+
+/// Radius drawn from the light of a Sérsic profile of index `n`
+fn sersic_radius(n: f32, effective_radius: f32, rnd: &mut StdRng) -> f32 {
+    // The light inside r is the regularized gamma function of shape 2n at b_n (r/r_e)^(1/n)
+    effective_radius * (gamma(2. * n, rnd) / sersic_b(n)).powf(n)
+}
+
+/// Gamma distributed value by Marsaglia & Tsang, `shape` at least 1
+fn gamma(shape: f32, rnd: &mut StdRng) -> f32 {
+    let d = shape - 1. / 3.;
+    let c = 1. / (9. * d).sqrt();
+    loop {
+        let x = normal(rnd);
+        let v = 1. + c * x;
+        if v <= 0. {
+            continue;
+        }
+        let v = v * v * v;
+        let u = rnd.gen::<f32>();
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// Standard normal value by Box-Muller
+fn normal(rnd: &mut StdRng) -> f32 {
+    let u = 1. - rnd.gen::<f32>();
+    (-2. * u.ln()).sqrt() * (f32::consts::TAU * rnd.gen::<f32>()).cos()
+}
+
+/// Star on one of the elongated orbits along the bar that make it up
+fn create_bar_star(galaxy_setting: &GalaxySettings, rnd: &mut StdRng) -> Star {
+    // About as dense along the whole bar, ending sharply at its ends
+    let a = galaxy_setting.bar_length * rnd.gen::<f32>().sqrt();
+    let b = a * galaxy_setting.bar_axis_ratio * (0.8 + 0.4 * rnd.gen::<f32>());
+    let tilt_scatter = 0.05 * (rnd.gen::<f32>() - 0.5);
+    let temp = (3000. * rnd.gen::<f32>() - 1500.) + 5000.;
+    let mag = 0.1 + 0.4 * rnd.gen::<f32>();
+    Star {
+        theta0: 360.0 * rnd.gen::<f32>(),
+        vel_theta: galaxy_setting.get_orbital_velocity(((a + b) / 2.).max(1.)),
+        tilt_angle: galaxy_setting.get_bar_angle() + tilt_scatter,
+        a,
+        b,
+        temp,
+        mag,
+        is_bar: true,
+    }
+}
+
+pub fn create_dusts(
+    galaxy_setting: &GalaxySettings,
+    density_wave: &DensityWave,
+    stream_seed: u64,
+    range: Range<usize>,
+) -> Vec<Star> {
+    // All dust shares one magnitude
+    let mag = 0.02 + 0.15 * StdRng::seed_from_u64(stream_seed).gen::<f32>();
+    let bright_count = bright_count(ObjectKind::Dust, galaxy_setting);
+    (range.start..range.end.min(galaxy_setting.count_dusts))
+        .map(|i| {
+            let mut rnd = object_rng(stream_seed, i);
+            create_dust(
+                galaxy_setting,
+                density_wave,
+                i,
+                mag,
+                i < bright_count,
+                &mut rnd,
+            )
+        })
+        .collect()
+}
+
+fn create_dust(
+    galaxy_setting: &GalaxySettings,
+    density_wave: &DensityWave,
+    index: usize,
+    mag: f32,
+    is_bright: bool,
+    rnd: &mut StdRng,
+) -> Star {
+    // Every other dust follows the density wave, the rest is spread evenly
+    let rad = match index % 2 {
+        0 => density_wave.val_from_prob(rnd.gen()),
+        _ => {
+            let x: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
+            let y: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
+            f32::sqrt(x * x + y * y)
+        }
+    };
+
+    let temp = galaxy_setting.base_temp + rad / 4.5;
+    let b = rad * galaxy_setting.get_excentricity(rad);
+    let mut star = if is_on_log_spiral_arm(galaxy_setting, rad) {
+        create_arm_star(galaxy_setting, rad, temp, mag, rnd)
+    } else {
+        let mut star = Star {
+            theta0: 360.0 * rnd.gen::<f32>(),
+            vel_theta: galaxy_setting.get_orbital_velocity((rad + b) / 2.),
+            tilt_angle: get_disc_tilt(galaxy_setting, rad, rnd),
+            a: rad,
+            b: rad * galaxy_setting.get_excentricity(rad),
+            temp,
+            mag,
+            is_bar: false,
+        };
+        disturb_ellipse(galaxy_setting, &mut star);
+        star
+    };
+
+    // Make a small portion of the stars brighter
+    if is_bright {
+        star.mag = 1_f32.min(star.mag + 0.1 + rnd.gen::<f32>() * 0.4);
+    }
+    star
+}
+
+// Most objects a filament is made of
+const FILAMENT_FACTOR: usize = 100;
+
+/// Number of objects of the filament, drawn first from its stream
+fn filament_len(rnd: &mut StdRng) -> usize {
+    (FILAMENT_FACTOR as f32 * rnd.gen::<f32>()) as usize
+}
+
+pub fn create_dusts_filaments(
+    galaxy_setting: &GalaxySettings,
+    stream_seed: u64,
+    range: Range<usize>,
+) -> Vec<Star> {
+    let mut stars: Vec<Star> = vec![];
+    let mut first = 0;
+    for filament in 0..galaxy_setting.count_dusts_filaments / FILAMENT_FACTOR {
+        if first >= range.end {
+            break;
+        }
+        let mut rnd = object_rng(stream_seed, filament);
+        let num = filament_len(&mut rnd);
+        let indices = first..first + num;
+        first += num;
+        if indices.end <= range.start {
+            continue;
+        }
+        let objects = create_filament(galaxy_setting, num, &mut rnd);
+        stars.extend(
+            objects
+                .into_iter()
+                .zip(indices)
+                .filter(|(_, i)| range.contains(i))
+                .map(|(star, _)| star),
+        );
+    }
+    stars
+}
+
+fn create_filament(galaxy_setting: &GalaxySettings, num: usize, rnd: &mut StdRng) -> Vec<Star> {
+    let x: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
+    let y: f32 = 2. * galaxy_setting.radius * rnd.gen::<f32>() - galaxy_setting.radius;
+    let mut rad = f32::sqrt(x * x + y * y);
+
+    let theta = 360.0 * rnd.gen::<f32>();
+    let mag = 0.1 + 0.05 * rnd.gen::<f32>();
+    let temp = galaxy_setting.base_temp + rad / 4.5 - 1000.;
+    let b = rad * galaxy_setting.get_excentricity(rad);
+    // On logarithmic arms a filament follows one arm at a fixed distance across it
+    let arm_offset = galaxy_setting
+        .has_log_spiral_arms()
+        .then(|| draw_arm_offset(galaxy_setting, rnd));
+    let mut stars: Vec<Star> = vec![];
+    for _j in 0..num {
+        rad = rad + 200. - 400. * rnd.gen::<f32>();
+        let theta0 = theta + 10. - 20. * rnd.gen::<f32>();
+        let mag = mag + 0.025 * rnd.gen::<f32>();
+        stars.push(match arm_offset {
+            Some((arm, offset)) if is_on_log_spiral_arm(galaxy_setting, rad) => {
+                arm_orbit(galaxy_setting, arm, offset, rad, temp, mag)
+            }
+            _ => Star {
+                theta0,
+                vel_theta: galaxy_setting.get_orbital_velocity((rad + b) / 2.),
+                tilt_angle: galaxy_setting.get_angular_offset(rad),
+                a: rad,
+                b: rad * galaxy_setting.get_excentricity(rad),
+                temp,
+                mag,
+                is_bar: false,
+            },
+        });
+    }
+    stars
+}
@@ -0,0 +1,20 @@
+//! Galaxy generation without any window, GPU or ECS.
+//!
+//! The same seed, `GalaxySettings` and `DensityWave` produce exactly the objects
+//! that the windowed application spawns.
+
+pub mod appearance;
+pub mod bookmark;
+pub mod camera_path;
+pub mod density_wave;
+pub mod export;
+pub mod frames;
+pub mod galaxy_setting_component;
+pub mod generator;
+pub mod overrides;
+pub mod preset;
+pub mod render;
+pub mod snapshot;
+pub mod spatial_index;
+pub mod star;
+pub mod validation;
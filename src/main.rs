@@ -1,14 +1,36 @@
 use bevy::prelude::*;
+use clap::Parser;
+use std::process;
 
 mod app_plugin;
 mod app_ui_plugin;
+mod cli;
 mod pan_cam;
 
 fn main() {
+    let cli = cli::Cli::parse();
+    let result = match cli.command.unwrap_or(cli::Command::View(cli.view)) {
+        cli::Command::View(args) => cli::view_command::view_options(&args).map(view),
+        cli::Command::Render(args) => cli::render_command::run(&args),
+        cli::Command::Export(args) => cli::export_command::run(&args),
+        cli::Command::Snapshot(args) => cli::snapshot_command::run(&args),
+        cli::Command::Frames(args) => cli::frames_command::run(&args),
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn view(options: app_plugin::view_options_resource::ViewOptions) {
     App::new()
-        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPlugins.set(AssetPlugin {
+            // Reload settings json when it is edited
+            watch_for_changes: true,
+            ..default()
+        }))
         .add_plugin(pan_cam::PanCamPlugin)
-        .add_plugin(app_plugin::AppPlugin)
+        .add_plugin(app_plugin::AppPlugin { options })
         .add_plugin(app_ui_plugin::AppUIPlugin)
         // Loging
         // .add_plugin(bevy::diagnostic::LogDiagnosticsPlugin::default())
@@ -0,0 +1,129 @@
+//! Per-field overrides of the settings files, like `radius=15000` or `wave.k=0.03`.
+//!
+//! Overridden settings go through validation again, so an override that breaks
+//! generation falls back like a bad value in the file would.
+
+use serde_json::Value;
+use std::str::FromStr;
+
+use super::density_wave::DensityWave;
+use super::galaxy_setting_component::GalaxySettings;
+use super::validation::{self, ConfigProblem};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Section {
+    GalaxySettings,
+    DensityWave,
+}
+
+impl Section {
+    fn field_names(&self) -> Vec<String> {
+        let value = match self {
+            Section::GalaxySettings => serde_json::to_value(GalaxySettings::default()),
+            Section::DensityWave => serde_json::to_value(DensityWave::default()),
+        };
+        match value {
+            Ok(Value::Object(map)) => map.keys().cloned().collect(),
+            _ => vec![],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Override {
+    pub section: Section,
+    pub field: String,
+    pub value: Value,
+}
+
+impl Override {
+    pub fn new(section: Section, field: &str, value: impl Into<Value>) -> Self {
+        Override {
+            section,
+            field: field.to_string(),
+            value: value.into(),
+        }
+    }
+}
+
+impl FromStr for Override {
+    type Err = String;
+
+    /// `field=value` for `GalaxySettings`, `wave.field=value` for `DensityWave`.
+    /// The value is read as json, or as a string if it is not valid json.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (key, value) = text
+            .split_once('=')
+            .ok_or_else(|| format!("expected field=value, got {}", text))?;
+        let key = key.trim();
+        let (section, field) = match key.split_once('.') {
+            Some(("wave" | "density_wave", field)) => (Section::DensityWave, field),
+            Some(("galaxy" | "galaxy_settings", field)) => (Section::GalaxySettings, field),
+            Some(_) => return Err(format!("unknown section in {}", key)),
+            None => (Section::GalaxySettings, key),
+        };
+
+        let field_names = section.field_names();
+        if !field_names.iter().any(|name| name == field) {
+            return Err(format!(
+                "unknown field {}, expected one of: {}",
+                key,
+                field_names.join(", ")
+            ));
+        }
+
+        let value = value.trim();
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::from(value));
+        Ok(Override::new(section, field, value))
+    }
+}
+
+/// Overrides applied in order, later ones win
+#[derive(Clone, Debug, Default)]
+pub struct Overrides(pub Vec<Override>);
+
+impl Overrides {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn apply_to_galaxy(&self, settings: &mut GalaxySettings) -> Vec<ConfigProblem> {
+        let Some(value) = self.apply_to_value(Section::GalaxySettings, &*settings) else {
+            return vec![];
+        };
+        let (overridden, problems) = validation::galaxy_settings_from_value(&value);
+        *settings = overridden;
+        problems
+    }
+
+    /// The wave is built afterwards, fallbacks are scaled to `galaxy`
+    pub fn apply_to_wave(
+        &self,
+        wave: &mut DensityWave,
+        galaxy: &GalaxySettings,
+    ) -> Vec<ConfigProblem> {
+        let Some(value) = self.apply_to_value(Section::DensityWave, &*wave) else {
+            return vec![];
+        };
+        let (overridden, problems) = validation::density_wave_from_value(&value, galaxy);
+        *wave = overridden;
+        problems
+    }
+
+    fn apply_to_value(&self, section: Section, settings: &impl serde::Serialize) -> Option<Value> {
+        let overrides: Vec<&Override> = self
+            .0
+            .iter()
+            .filter(|item| item.section == section)
+            .collect();
+        if overrides.is_empty() {
+            return None;
+        }
+
+        let mut value = serde_json::to_value(settings).ok()?;
+        for item in overrides {
+            value[item.field.as_str()] = item.value.clone();
+        }
+        Some(value)
+    }
+}
@@ -2,7 +2,14 @@ use bevy::{
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
     render::camera::OrthographicProjection,
+    transform::TransformSystem,
 };
+use std::f32::consts::FRAC_PI_2;
+
+/// Glides slower than this many screen pixels per second stop
+const MIN_GLIDE_SPEED: f32 = 5.;
+/// Difference of the log scales at which an eased zoom is done
+const ZOOM_SNAP: f32 = 0.001;
 
 /// Plugin that adds the necessary systems for `PanCam` components to work
 #[derive(Default)]
@@ -10,22 +17,26 @@ pub struct PanCamPlugin;
 
 impl Plugin for PanCamPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(camera_movement).add_system(camera_zoom);
+        app.add_system(camera_movement)
+            .add_system(camera_zoom)
+            .add_system(camera_navigation)
+            .add_system(camera_fly)
+            // After the target moved, before the camera transform is propagated
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                camera_follow.before(TransformSystem::TransformPropagate),
+            );
     }
 }
 
+/// Zoom with the mouse wheel, at once or eased toward a target scale with `zoom_smoothing`
 pub fn camera_zoom(
-    mut query: Query<(&PanCam, &mut OrthographicProjection, &mut Transform)>,
+    time: Res<Time>,
+    mut query: Query<(&mut PanCam, &mut OrthographicProjection, &mut Transform)>,
     mut scroll_events: EventReader<MouseWheel>,
     windows: Res<Windows>,
     #[cfg(feature = "bevy_egui")] egui_ctx: Option<ResMut<bevy_egui::EguiContext>>,
 ) {
-    #[cfg(feature = "bevy_egui")]
-    if let Some(mut egui_ctx) = egui_ctx {
-        if egui_ctx.ctx_mut().wants_pointer_input() || egui_ctx.ctx_mut().wants_keyboard_input() {
-            return;
-        }
-    }
     let pixels_per_line = 100.; // Maybe make configurable?
     let scroll = scroll_events
         .iter()
@@ -35,9 +46,17 @@ pub fn camera_zoom(
         })
         .sum::<f32>();
 
-    if scroll == 0. {
-        return;
-    }
+    // Still eased toward the target while the pointer is over egui
+    #[cfg(feature = "bevy_egui")]
+    let scroll = match egui_ctx {
+        Some(mut egui_ctx)
+            if egui_ctx.ctx_mut().wants_pointer_input()
+                || egui_ctx.ctx_mut().wants_keyboard_input() =>
+        {
+            0.
+        }
+        _ => scroll,
+    };
 
     let window = windows.get_primary().unwrap();
     let window_size = Vec2::new(window.width(), window.height());
@@ -45,69 +64,285 @@ pub fn camera_zoom(
         .cursor_position()
         .map(|cursor_pos| (cursor_pos / window_size) * 2. - Vec2::ONE);
 
-    for (cam, mut proj, mut pos) in &mut query {
-        if cam.enabled {
-            let old_scale = proj.scale;
-            proj.scale = (proj.scale * (1. + -scroll * 0.001)).max(cam.min_scale);
-
-            if let Some(max_scale) = cam.max_scale {
-                proj.scale = proj.scale.min(max_scale);
-            }
-
-            if let (Some(mouse_normalized_screen_pos), true) =
-                (mouse_normalized_screen_pos, cam.zoom_to_cursor)
-            {
-                let proj_size = Vec2::new(proj.right, proj.top);
-                let mouse_world_pos = pos.translation.truncate()
-                    + mouse_normalized_screen_pos * proj_size * old_scale;
-                pos.translation = (mouse_world_pos
-                    - mouse_normalized_screen_pos * proj_size * proj.scale)
-                    .extend(pos.translation.z);
+    for (mut cam, mut proj, mut pos) in &mut query {
+        if scroll != 0. && cam.enabled {
+            cam.flight = None;
+            let anchor = match (mouse_normalized_screen_pos, cam.zoom_to_cursor) {
+                (Some(mouse_normalized_screen_pos), true) => mouse_normalized_screen_pos,
+                _ => Vec2::ZERO,
+            };
+            let factor = 1. + -scroll * 0.001;
+            if cam.zoom_smoothing.is_some() {
+                // Wheel events add up on the target, the scale catches up below
+                let target = cam.zoom_target.unwrap_or(proj.scale) * factor;
+                cam.zoom_target = Some(cam.clamp_scale(target));
+                cam.zoom_anchor = anchor;
+            } else {
+                let scale = cam.clamp_scale(proj.scale * factor);
+                zoom_view(&mut cam, &mut proj, &mut pos, scale, anchor);
             }
         }
+
+        let (Some(target), Some(smoothing)) = (cam.zoom_target, cam.zoom_smoothing) else {
+            continue;
+        };
+        // Eased in log space, so zooming in and out feel the same
+        let t = 1. - (-smoothing * time.delta_seconds()).exp();
+        let log_scale = proj.scale.ln() + (target.ln() - proj.scale.ln()) * t;
+        let scale = if (log_scale - target.ln()).abs() < ZOOM_SNAP {
+            cam.zoom_target = None;
+            target
+        } else {
+            log_scale.exp()
+        };
+        let anchor = cam.zoom_anchor;
+        zoom_view(&mut cam, &mut proj, &mut pos, scale, anchor);
     }
 }
 
+/// Drag to pan, with `pan_friction` the view keeps gliding after the button is released
 pub fn camera_movement(
+    time: Res<Time>,
     windows: Res<Windows>,
     mouse_buttons: Res<Input<MouseButton>>,
-    mut query: Query<(&PanCam, &mut Transform, &OrthographicProjection)>,
+    mut query: Query<(&mut PanCam, &mut Transform, &OrthographicProjection)>,
     mut last_pos: Local<Option<Vec2>>,
     #[cfg(feature = "bevy_egui")] egui_ctx: Option<ResMut<bevy_egui::EguiContext>>,
 ) {
-    #[cfg(feature = "bevy_egui")]
-    if let Some(mut egui_ctx) = egui_ctx {
-        if egui_ctx.ctx_mut().wants_pointer_input() || egui_ctx.ctx_mut().wants_keyboard_input() {
-            *last_pos = None;
-            return;
-        }
-    }
-
     let window = windows.get_primary().unwrap();
 
     // Use position instead of MouseMotion, otherwise we don't get acceleration movement
-    let current_pos = match window.cursor_position() {
-        Some(current_pos) => current_pos,
-        None => return,
+    let current_pos = window.cursor_position();
+
+    #[cfg(feature = "bevy_egui")]
+    let current_pos = match egui_ctx {
+        Some(mut egui_ctx)
+            if egui_ctx.ctx_mut().wants_pointer_input()
+                || egui_ctx.ctx_mut().wants_keyboard_input() =>
+        {
+            None
+        }
+        _ => current_pos,
     };
-    let delta = current_pos - last_pos.unwrap_or(current_pos);
 
-    for (cam, mut transform, projection) in &mut query {
-        if cam.enabled
+    let delta_seconds = time.delta_seconds();
+
+    for (mut cam, mut transform, projection) in &mut query {
+        let is_dragging = cam.enabled
+            && current_pos.is_some()
             && cam
                 .grab_buttons
                 .iter()
-                .any(|btn| mouse_buttons.pressed(*btn))
-        {
+                .any(|btn| mouse_buttons.pressed(*btn));
+
+        if is_dragging {
+            let current_pos = current_pos.unwrap();
+            let delta = current_pos - last_pos.unwrap_or(current_pos);
             let scaling = Vec2::new(
                 window.width() / (projection.right - projection.left),
                 window.height() / (projection.top - projection.bottom),
             ) * projection.scale;
 
-            transform.translation -= (delta * scaling).extend(0.);
+            let shift = -(delta * scaling);
+            pan_view(&mut cam, &mut transform, shift);
+            cam.flight = None;
+            if delta_seconds > 0. {
+                // Smoothed, a single jerky frame doesn't fling the view away
+                cam.pan_velocity = cam.pan_velocity.lerp(shift / delta_seconds, 0.5);
+            }
+        } else if let (Some(friction), true) = (cam.pan_friction, cam.enabled) {
+            if cam.pan_velocity != Vec2::ZERO {
+                let shift = cam.pan_velocity * delta_seconds;
+                pan_view(&mut cam, &mut transform, shift);
+                cam.pan_velocity *= (-friction * delta_seconds).exp();
+                if cam.pan_velocity.length() < MIN_GLIDE_SPEED * projection.scale {
+                    cam.pan_velocity = Vec2::ZERO;
+                }
+            }
+        } else if cam.pan_velocity != Vec2::ZERO {
+            cam.pan_velocity = Vec2::ZERO;
+        }
+    }
+    *last_pos = current_pos;
+}
+
+/// Move the view by `shift` in the camera frame
+fn pan_view(cam: &mut PanCam, transform: &mut Transform, shift: Vec2) {
+    if cam.follow.is_some() {
+        cam.follow_offset += shift;
+    } else {
+        let rotation = transform.rotation;
+        transform.translation += rotation * shift.extend(0.);
+    }
+}
+
+/// Set the scale, keeping the point at `anchor` in place, -1 to 1 across the screen
+fn zoom_view(
+    cam: &mut PanCam,
+    proj: &mut OrthographicProjection,
+    transform: &mut Transform,
+    scale: f32,
+    anchor: Vec2,
+) {
+    let proj_size = Vec2::new(proj.right, proj.top);
+    let shift = anchor * proj_size * (proj.scale - scale);
+    proj.scale = scale;
+    pan_view(cam, transform, shift);
+}
+
+/// Pan and zoom with the keys and gamepad controls configured on the `PanCam`.
+/// Speeds are in screen units, so they feel the same at every zoom level.
+pub fn camera_navigation(
+    time: Res<Time>,
+    keyboard: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Axis<GamepadButton>>,
+    mut query: Query<(&mut PanCam, &mut OrthographicProjection, &mut Transform)>,
+) {
+    // Keys held with Ctrl are shortcuts, like Ctrl+S
+    let is_shortcut = keyboard.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+    let is_pressed =
+        |keys: &[KeyCode]| !is_shortcut && keys.iter().any(|key| keyboard.pressed(*key));
+    let delta_seconds = time.delta_seconds();
+
+    for (mut cam, mut proj, mut transform) in &mut query {
+        if !cam.enabled {
+            continue;
+        }
+
+        // Direction on screen and zoom, -1 to 1 on every axis
+        let mut direction = Vec2::ZERO;
+        let mut zoom = 0.;
+        for (keys, key_direction) in [
+            (&cam.pan_up_keys, Vec2::Y),
+            (&cam.pan_down_keys, Vec2::NEG_Y),
+            (&cam.pan_left_keys, Vec2::NEG_X),
+            (&cam.pan_right_keys, Vec2::X),
+        ] {
+            if is_pressed(keys) {
+                direction += key_direction;
+            }
+        }
+        if is_pressed(&cam.zoom_in_keys) {
+            zoom -= 1.;
+        }
+        if is_pressed(&cam.zoom_out_keys) {
+            zoom += 1.;
+        }
+
+        for gamepad in gamepads.iter() {
+            if let Some((x_axis, y_axis)) = cam.gamepad_pan_axes {
+                let axis_value = |axis| {
+                    gamepad_axes
+                        .get(GamepadAxis::new(gamepad, axis))
+                        .unwrap_or(0.)
+                };
+                direction += Vec2::new(axis_value(x_axis), axis_value(y_axis));
+            }
+            let button_value = |button: Option<GamepadButtonType>| {
+                button
+                    .and_then(|button| gamepad_buttons.get(GamepadButton::new(gamepad, button)))
+                    .unwrap_or(0.)
+            };
+            zoom -= button_value(cam.gamepad_zoom_in_button);
+            zoom += button_value(cam.gamepad_zoom_out_button);
+        }
+
+        let direction = direction.clamp_length_max(1.);
+        let zoom = zoom.clamp(-1., 1.);
+
+        if direction != Vec2::ZERO {
+            let shift = direction * cam.pan_speed * proj.scale * delta_seconds;
+            pan_view(&mut cam, &mut transform, shift);
+            cam.pan_velocity = Vec2::ZERO;
+            cam.flight = None;
+        }
+
+        if zoom != 0. {
+            // Held keys zoom smoothly already, a wheel zoom still easing is dropped
+            let scale = proj.scale * cam.zoom_speed.powf(zoom * delta_seconds);
+            proj.scale = cam.clamp_scale(scale);
+            cam.zoom_target = None;
+            cam.flight = None;
+        }
+    }
+}
+
+/// Move cameras along their flight, eased in and out.
+/// The scale changes evenly in log space and the center moves with it,
+/// so the destination comes into view at a steady pace.
+pub fn camera_fly(
+    time: Res<Time>,
+    mut query: Query<(&mut PanCam, &mut OrthographicProjection, &mut Transform)>,
+) {
+    for (mut cam, mut proj, mut transform) in &mut query {
+        let duration = cam.fly_duration;
+        let Some(flight) = cam.flight.as_mut() else {
+            continue;
+        };
+        let (from, from_scale) = *flight
+            .from
+            .get_or_insert((transform.translation.truncate(), proj.scale));
+        flight.elapsed += time.delta_seconds();
+
+        let t = match duration > 0. {
+            true => (flight.elapsed / duration).min(1.),
+            false => 1.,
+        };
+        let t = t * t * (3. - 2. * t);
+        let log_scale = from_scale.ln() + (flight.to_scale.ln() - from_scale.ln()) * t;
+        let scale = log_scale.exp();
+        // Move by the share of the zoom that is done, plain easing without zoom
+        let progress = if (flight.to_scale.ln() - from_scale.ln()).abs() > ZOOM_SNAP {
+            (scale - from_scale) / (flight.to_scale - from_scale)
+        } else {
+            t
+        };
+        let center = from.lerp(flight.to, progress);
+
+        transform.translation = center.extend(transform.translation.z);
+        proj.scale = scale;
+        if t >= 1. {
+            cam.flight = None;
+        }
+    }
+}
+
+/// Keep following cameras on their target, turned with it around the origin if co-rotating.
+/// Without a target a co-rotating camera is turned upright again.
+pub fn camera_follow(
+    mut query: Query<(&mut PanCam, &mut Transform)>,
+    targets: Query<&Transform, Without<PanCam>>,
+) {
+    for (mut cam, mut transform) in &mut query {
+        let Some(target) = cam.follow else {
+            if cam.co_rotate && transform.rotation != Quat::IDENTITY {
+                transform.rotation = Quat::IDENTITY;
+            }
+            continue;
+        };
+        let Ok(target_transform) = targets.get(target) else {
+            // The target is gone, stay where it was last seen
+            cam.unfollow();
+            continue;
+        };
+
+        let target_pos = target_transform.translation.truncate();
+        let rotation = if cam.co_rotate && target_pos != Vec2::ZERO {
+            // The origin is always straight down
+            Quat::from_rotation_z(target_pos.y.atan2(target_pos.x) - FRAC_PI_2)
+        } else {
+            Quat::IDENTITY
+        };
+        let translation =
+            target_pos.extend(transform.translation.z) + rotation * cam.follow_offset.extend(0.);
+
+        if transform.translation != translation || transform.rotation != rotation {
+            transform.translation = translation;
+            transform.rotation = rotation;
         }
     }
-    *last_pos = Some(current_pos);
 }
 
 /// A component that adds panning camera controls to an orthographic camera
@@ -131,6 +366,94 @@ pub struct PanCam {
     /// If present, the orthographic projection's scale will be clamped at
     /// this value when zooming out.
     pub max_scale: Option<f32>,
+    /// Entity the camera stays on, panning and zooming move the view around it
+    pub follow: Option<Entity>,
+    /// Offset of the view from the followed entity, in the camera frame
+    pub follow_offset: Vec2,
+    /// When following, turn the camera with the target around the world origin,
+    /// so the target's neighbourhood keeps its orientation
+    pub co_rotate: bool,
+    /// Keys that pan the view, held down to keep moving
+    pub pan_up_keys: Vec<KeyCode>,
+    pub pan_down_keys: Vec<KeyCode>,
+    pub pan_left_keys: Vec<KeyCode>,
+    pub pan_right_keys: Vec<KeyCode>,
+    /// Keys that zoom towards the middle of the screen
+    pub zoom_in_keys: Vec<KeyCode>,
+    pub zoom_out_keys: Vec<KeyCode>,
+    /// Gamepad stick that pans the view, as its x and y axes
+    pub gamepad_pan_axes: Option<(GamepadAxisType, GamepadAxisType)>,
+    /// Gamepad buttons that zoom, analog triggers zoom by how far they are pressed
+    pub gamepad_zoom_in_button: Option<GamepadButtonType>,
+    pub gamepad_zoom_out_button: Option<GamepadButtonType>,
+    /// Keyboard and gamepad panning speed in screen pixels per second
+    pub pan_speed: f32,
+    /// Factor the scale changes by in one second of keyboard or gamepad zooming
+    pub zoom_speed: f32,
+    /// When present, the view keeps gliding after a drag and slows down at this rate per second.
+    /// Higher values stop sooner.
+    pub pan_friction: Option<f32>,
+    /// Current glide, in world units per second in the camera frame
+    pub pan_velocity: Vec2,
+    /// When present, wheel zooming eases toward the target scale at this rate per second,
+    /// instead of jumping. Higher values are snappier.
+    pub zoom_smoothing: Option<f32>,
+    /// Scale an eased zoom is heading to
+    pub zoom_target: Option<f32>,
+    /// Screen point kept in place by an eased zoom, -1 to 1 across the screen
+    pub zoom_anchor: Vec2,
+    /// Seconds a `fly_to` takes
+    pub fly_duration: f32,
+    /// Animated move in progress, user input cancels it
+    pub flight: Option<Flight>,
+}
+
+/// Animated move of a camera to a view, see `PanCam::fly_to`
+#[derive(Clone, Debug)]
+pub struct Flight {
+    pub to: Vec2,
+    pub to_scale: f32,
+    /// Taken from the camera on the first step
+    from: Option<(Vec2, f32)>,
+    elapsed: f32,
+}
+
+impl PanCam {
+    /// Center the camera on the entity and keep it there
+    pub fn follow(&mut self, target: Entity) {
+        self.follow = Some(target);
+        self.follow_offset = Vec2::ZERO;
+        self.pan_velocity = Vec2::ZERO;
+        self.flight = None;
+    }
+
+    pub fn unfollow(&mut self) {
+        self.follow = None;
+        self.follow_offset = Vec2::ZERO;
+        self.pan_velocity = Vec2::ZERO;
+    }
+
+    /// Move the view to `center` in world units and `scale` in `fly_duration` seconds.
+    /// Following stops, the view would be pulled back to the target otherwise.
+    pub fn fly_to(&mut self, center: Vec2, scale: f32) {
+        self.unfollow();
+        self.zoom_target = None;
+        self.flight = Some(Flight {
+            to: center,
+            to_scale: self.clamp_scale(scale),
+            from: None,
+            elapsed: 0.,
+        });
+    }
+
+    /// Keep a projection scale between the minimum and maximum
+    pub fn clamp_scale(&self, scale: f32) -> f32 {
+        let scale = scale.max(self.min_scale);
+        match self.max_scale {
+            Some(max_scale) => scale.min(max_scale),
+            None => scale,
+        }
+    }
 }
 
 impl Default for PanCam {
@@ -141,6 +464,27 @@ impl Default for PanCam {
             zoom_to_cursor: true,
             min_scale: 0.00001,
             max_scale: None,
+            follow: None,
+            follow_offset: Vec2::ZERO,
+            co_rotate: false,
+            pan_up_keys: vec![KeyCode::W, KeyCode::Up],
+            pan_down_keys: vec![KeyCode::S, KeyCode::Down],
+            pan_left_keys: vec![KeyCode::A, KeyCode::Left],
+            pan_right_keys: vec![KeyCode::D, KeyCode::Right],
+            zoom_in_keys: vec![KeyCode::Equals, KeyCode::Plus, KeyCode::NumpadAdd],
+            zoom_out_keys: vec![KeyCode::Minus, KeyCode::NumpadSubtract],
+            gamepad_pan_axes: Some((GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY)),
+            gamepad_zoom_in_button: Some(GamepadButtonType::RightTrigger2),
+            gamepad_zoom_out_button: Some(GamepadButtonType::LeftTrigger2),
+            pan_speed: 600.,
+            zoom_speed: 4.,
+            pan_friction: None,
+            pan_velocity: Vec2::ZERO,
+            zoom_smoothing: None,
+            zoom_target: None,
+            zoom_anchor: Vec2::ZERO,
+            fly_duration: 1.5,
+            flight: None,
         }
     }
 }
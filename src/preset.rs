@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::bookmark::CameraBookmark;
+use super::density_wave::DensityWave;
+use super::galaxy_setting_component::GalaxySettings;
+use super::validation::{self, ConfigProblem};
+
+pub const PRESETS_DIR: &str = "presets";
+const PRESET_EXTENSION: &str = "json";
+
+/// Galaxy and density wave settings stored together in one file
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub galaxy_settings: GalaxySettings,
+    pub density_wave: DensityWave,
+    /// Camera views of this galaxy, optional in the file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bookmarks: Vec<CameraBookmark>,
+}
+
+#[derive(Debug)]
+pub enum PresetError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresetError::Io(err) => write!(f, "{}", err),
+            PresetError::Json(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for PresetError {}
+
+impl From<io::Error> for PresetError {
+    fn from(err: io::Error) -> Self {
+        PresetError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for PresetError {
+    fn from(err: serde_json::Error) -> Self {
+        PresetError::Json(err)
+    }
+}
+
+impl Preset {
+    /// Read a preset, the density wave is built and ready to use.
+    /// Invalid or missing fields fall back to defaults and are returned as problems.
+    pub fn load(path: &Path) -> Result<(Preset, Vec<ConfigProblem>), PresetError> {
+        let file = fs::read_to_string(path)?;
+        let value: serde_json::Value = serde_json::from_str(&file)?;
+        Ok(Preset::from_value(&value))
+    }
+
+    pub fn from_value(value: &serde_json::Value) -> (Preset, Vec<ConfigProblem>) {
+        let mut problems = vec![];
+
+        let (galaxy_settings, galaxy_problems) = match value.get("galaxy_settings") {
+            Some(section) => validation::galaxy_settings_from_value(section),
+            None => (
+                GalaxySettings::default(),
+                vec![ConfigProblem::new("", "missing, using defaults")],
+            ),
+        };
+        problems.extend(
+            galaxy_problems
+                .into_iter()
+                .map(|problem| problem.in_section("galaxy_settings")),
+        );
+
+        let (density_wave, wave_problems) = match value.get("density_wave") {
+            Some(section) => validation::density_wave_from_value(section, &galaxy_settings),
+            None => {
+                let mut wave = DensityWave::for_galaxy(&galaxy_settings);
+                wave.build();
+                (
+                    wave,
+                    vec![ConfigProblem::new("", "missing, using defaults")],
+                )
+            }
+        };
+        problems.extend(
+            wave_problems
+                .into_iter()
+                .map(|problem| problem.in_section("density_wave")),
+        );
+
+        // Older presets have no bookmarks, that is not a problem
+        let (bookmarks, bookmark_problems) = match value.get("bookmarks") {
+            Some(section) => validation::bookmarks_from_value(section),
+            None => (vec![], vec![]),
+        };
+        problems.extend(
+            bookmark_problems
+                .into_iter()
+                .map(|problem| problem.in_section("bookmarks")),
+        );
+
+        let preset = Preset {
+            galaxy_settings,
+            density_wave,
+            bookmarks,
+        };
+        (preset, problems)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), PresetError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+// Not `with_extension`, it would replace the end of names with a dot like `m.31`
+pub fn preset_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.{}", name, PRESET_EXTENSION))
+}
+
+/// Name typed by the user made fit for a file name, `None` if nothing is left.
+/// Letters, digits, `-` and `_` are kept, spaces become `_`.
+pub fn clean_name(text: &str) -> Option<String> {
+    let name: String = text
+        .trim()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('_'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Give a preset another name, an existing preset with that name is not replaced
+pub fn rename_preset(dir: &Path, from: &str, to: &str) -> io::Result<()> {
+    let target = preset_path(dir, to);
+    if target.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to),
+        ));
+    }
+    fs::rename(preset_path(dir, from), target)
+}
+
+/// Names of all presets in the directory, sorted
+pub fn list_presets(dir: &Path) -> Vec<String> {
+    list_names(dir, PRESET_EXTENSION)
+}
+
+/// First free name of the form `preset_001`
+pub fn next_free_name(dir: &Path) -> String {
+    next_free_name_with_prefix(dir, PRESET_EXTENSION, "preset")
+}
+
+/// Stems of the files with the extension in the directory, sorted
+pub(crate) fn list_names(dir: &Path, extension: &str) -> Vec<String> {
+    let mut names: Vec<String> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(extension))
+            .filter_map(|path| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+    names.sort();
+    names
+}
+
+pub(crate) fn next_free_name_with_prefix(dir: &Path, extension: &str, prefix: &str) -> String {
+    let names = list_names(dir, extension);
+    (1..)
+        .map(|i| format!("{}_{:03}", prefix, i))
+        .find(|name| !names.contains(name))
+        .unwrap()
+}
@@ -0,0 +1,373 @@
+//! Software rasterizer for machines without a GPU.
+//!
+//! Objects are drawn like the windowed application draws them: the same colors and
+//! sprite sizes, the same zoom dependent visibility, the particle texture and a port
+//! of the bloom pass of the camera. Sprites are blended additively in linear HDR and
+//! clamped like the untonemapped 2D camera output.
+
+use bevy_math::{Vec2, Vec3};
+use image::{Rgb, RgbImage};
+
+use super::appearance;
+use super::galaxy_setting_component::GalaxySettings;
+use super::generator::GalaxyObject;
+use super::star::ObjectKind;
+
+// Zoom ranges in which objects are visible, as the `LodSetting` of the application
+const STARS_VISIBILITY: (f32, f32) = (0., 10.);
+const OTHER_VISIBILITY: (f32, f32) = (1., 90.);
+
+const PARTICLE_PNG: &[u8] = include_bytes!("../assets/particle.png");
+
+/// Part of the galaxy to render, like the orthographic camera of the application
+#[derive(Clone, Debug)]
+pub struct View {
+    pub width: u32,
+    pub height: u32,
+    /// World position in the middle of the image
+    pub center: Vec2,
+    /// World units per pixel, the `OrthographicProjection` scale
+    pub scale: f32,
+    /// Epoch of the orbits
+    pub years: f64,
+}
+
+impl Default for View {
+    /// The view of the application when it starts
+    fn default() -> Self {
+        View {
+            width: 1280,
+            height: 720,
+            center: Vec2::ZERO,
+            scale: 70.,
+            years: 0.,
+        }
+    }
+}
+
+/// Same meaning as the `BloomSettings` of the camera
+#[derive(Clone, Debug)]
+pub struct Bloom {
+    pub threshold: f32,
+    pub knee: f32,
+    pub scale: f32,
+    pub intensity: f32,
+}
+
+impl Default for Bloom {
+    /// Bloom of the camera of the application
+    fn default() -> Self {
+        Bloom {
+            threshold: 0.19,
+            knee: 0.35,
+            scale: 5.0,
+            intensity: 10.0,
+        }
+    }
+}
+
+impl Bloom {
+    /// Bloom of the application at a zoom scale, the knee follows the zoom
+    pub fn for_scale(scale: f32) -> Self {
+        let knee = if scale < OTHER_VISIBILITY.0 {
+            remap(0., OTHER_VISIBILITY.0, 0.4, 0.16, scale)
+        } else if scale < OTHER_VISIBILITY.1 {
+            remap(OTHER_VISIBILITY.0, OTHER_VISIBILITY.1, 0.16, 0.35, scale)
+        } else {
+            0.35
+        };
+        Bloom {
+            knee,
+            ..Default::default()
+        }
+    }
+}
+
+/// Sprite texture, linear and premultiplied by its alpha
+pub struct Particle {
+    size: usize,
+    texels: Vec<Vec3>,
+    /// Average texel, used for sprites smaller than a pixel
+    mean: Vec3,
+}
+
+impl Particle {
+    pub fn from_image(image: &image::RgbaImage) -> Self {
+        let size = image.width().min(image.height()) as usize;
+        let mut texels = Vec::with_capacity(size * size);
+        for y in 0..size {
+            for x in 0..size {
+                let [r, g, b, a] = image.get_pixel(x as u32, y as u32).0;
+                let [r, g, b, a] = [r, g, b, a].map(|value| value as f32 / 255.);
+                texels.push(appearance::Rgba::new(r, g, b, a).to_linear_premultiplied());
+            }
+        }
+        let mean = texels.iter().copied().sum::<Vec3>() / texels.len().max(1) as f32;
+        Particle { size, texels, mean }
+    }
+
+    fn sample(&self, uv: Vec2) -> Vec3 {
+        sample_bilinear(&self.texels, self.size, self.size, uv)
+    }
+}
+
+impl Default for Particle {
+    /// `assets/particle.png`, the texture of the application sprites
+    fn default() -> Self {
+        let image = image::load_from_memory(PARTICLE_PNG)
+            .expect("particle.png is a valid png")
+            .to_rgba8();
+        Particle::from_image(&image)
+    }
+}
+
+/// Linear HDR color buffer
+struct Hdr {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec3>,
+}
+
+impl Hdr {
+    fn new(width: usize, height: usize) -> Self {
+        Hdr {
+            width,
+            height,
+            pixels: vec![Vec3::ZERO; width * height],
+        }
+    }
+
+    fn add(&mut self, x: i64, y: i64, color: Vec3) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            self.pixels[y as usize * self.width + x as usize] += color;
+        }
+    }
+
+    fn sample(&self, uv: Vec2) -> Vec3 {
+        sample_bilinear(&self.pixels, self.width, self.height, uv)
+    }
+
+    fn texel_size(&self) -> Vec2 {
+        Vec2::new(1. / self.width as f32, 1. / self.height as f32)
+    }
+
+    /// Fill a new buffer by evaluating `f` at the uv of every pixel center
+    fn from_fn(width: usize, height: usize, f: impl Fn(Vec2) -> Vec3) -> Self {
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let uv = Vec2::new(
+                    (x as f32 + 0.5) / width as f32,
+                    (y as f32 + 0.5) / height as f32,
+                );
+                pixels.push(f(uv));
+            }
+        }
+        Hdr {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+/// Render the objects, `bloom` is usually `Bloom::for_scale(view.scale)`
+pub fn render(
+    objects: &[GalaxyObject],
+    settings: &GalaxySettings,
+    view: &View,
+    bloom: Option<&Bloom>,
+    particle: &Particle,
+) -> RgbImage {
+    let mut hdr = Hdr::new(view.width as usize, view.height as usize);
+    let half_size = Vec2::new(view.width as f32, view.height as f32) / 2.;
+
+    for object in objects {
+        let fade = visibility(object.kind, view.scale);
+        if fade <= 0. {
+            continue;
+        }
+
+        let color =
+            appearance::object_color(object.kind, &object.star).to_linear_premultiplied() * fade;
+        let size = appearance::object_size(object.kind, &object.star, settings, view.years).abs()
+            / view.scale;
+
+        let position = object.star.position(settings, view.years);
+        let center = Vec2::new(
+            (position.x - view.center.x) / view.scale + half_size.x,
+            half_size.y - (position.y - view.center.y) / view.scale,
+        );
+        draw_sprite(&mut hdr, center, size, color, particle);
+    }
+
+    if let Some(bloom) = bloom {
+        apply_bloom(&mut hdr, bloom);
+    }
+
+    let mut image = RgbImage::new(view.width, view.height);
+    for (pixel, color) in image.pixels_mut().zip(&hdr.pixels) {
+        let color = color.to_array().map(appearance::linear_to_srgb);
+        *pixel = Rgb(color.map(to_u8));
+    }
+    image
+}
+
+/// Alpha multiplier of the zoom level, 0 when the object is hidden
+fn visibility(kind: ObjectKind, scale: f32) -> f32 {
+    match kind {
+        ObjectKind::Star if scale > STARS_VISIBILITY.0 && scale < STARS_VISIBILITY.1 => 1.,
+        ObjectKind::Star => 0.,
+        _ if scale > OTHER_VISIBILITY.0 && scale < OTHER_VISIBILITY.1 => {
+            remap(OTHER_VISIBILITY.0, OTHER_VISIBILITY.1, 0., 1., scale)
+        }
+        _ => 0.,
+    }
+}
+
+fn draw_sprite(hdr: &mut Hdr, center: Vec2, size: f32, color: Vec3, particle: &Particle) {
+    // Smaller than a pixel: spread the covered energy over the nearest pixels
+    if size < 1.5 {
+        let energy = color * particle.mean * size * size;
+        let position = center - Vec2::splat(0.5);
+        let base = position.floor();
+        let t = position - base;
+        let (x, y) = (base.x as i64, base.y as i64);
+        hdr.add(x, y, energy * (1. - t.x) * (1. - t.y));
+        hdr.add(x + 1, y, energy * t.x * (1. - t.y));
+        hdr.add(x, y + 1, energy * (1. - t.x) * t.y);
+        hdr.add(x + 1, y + 1, energy * t.x * t.y);
+        return;
+    }
+
+    let min = center - Vec2::splat(size / 2.);
+    let max = center + Vec2::splat(size / 2.);
+    if max.x < 0. || max.y < 0. || min.x > hdr.width as f32 || min.y > hdr.height as f32 {
+        return;
+    }
+    let x_range = (min.x.floor().max(0.) as i64)..(max.x.ceil().min(hdr.width as f32) as i64);
+    let y_range = (min.y.floor().max(0.) as i64)..(max.y.ceil().min(hdr.height as f32) as i64);
+    for y in y_range {
+        for x in x_range.clone() {
+            let uv = (Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - min) / size;
+            if uv.x < 0. || uv.y < 0. || uv.x > 1. || uv.y > 1. {
+                continue;
+            }
+            hdr.add(x, y, color * particle.sample(uv));
+        }
+    }
+}
+
+// Port of the bloom of the 2D camera: a thresholded 13 tap downsample chain
+// followed by tent filtered upsampling, added onto the image.
+fn apply_bloom(hdr: &mut Hdr, bloom: &Bloom) {
+    let min_view = (hdr.width.min(hdr.height) / 2) as u32;
+    let mip_count = calculate_mip_count(min_view) as usize;
+    let scale = bloom.scale * (min_view / 2u32.pow(mip_count as u32)) as f32 / 8.;
+
+    let curve = Vec3::new(
+        bloom.threshold - bloom.knee,
+        bloom.knee * 2.,
+        0.25 / bloom.knee,
+    );
+    let (mut width, mut height) = ((hdr.width / 2).max(1), (hdr.height / 2).max(1));
+    let mut mips = vec![Hdr::from_fn(width, height, |uv| {
+        let color = sample_13_tap(hdr, uv);
+        quadratic_threshold(color, bloom.threshold, curve).max(Vec3::splat(0.00001))
+    })];
+    for _ in 1..mip_count {
+        let source = mips.last().unwrap();
+        width = (width / 2).max(1);
+        height = (height / 2).max(1);
+        let mip = Hdr::from_fn(width, height, |uv| sample_13_tap(source, uv));
+        mips.push(mip);
+    }
+
+    let mut original = mips.pop().unwrap();
+    while let Some(up) = mips.pop() {
+        original = Hdr::from_fn(up.width, up.height, |uv| {
+            up.sample(uv) + sample_3x3_tent(&original, uv, scale)
+        });
+    }
+
+    let mut index = 0;
+    for y in 0..hdr.height {
+        for x in 0..hdr.width {
+            let uv = Vec2::new(
+                (x as f32 + 0.5) / hdr.width as f32,
+                (y as f32 + 0.5) / hdr.height as f32,
+            );
+            hdr.pixels[index] += sample_3x3_tent(&original, uv, scale) * bloom.intensity;
+            index += 1;
+        }
+    }
+}
+
+// Views smaller than 2 pixels still get the one mip
+fn calculate_mip_count(min_view: u32) -> u32 {
+    ((min_view.max(1) as f32).log2().round() as u32)
+        .saturating_sub(3)
+        .max(1)
+}
+
+fn quadratic_threshold(color: Vec3, threshold: f32, curve: Vec3) -> Vec3 {
+    let br = color.max_element();
+    let mut rq = (br - curve.x).clamp(0., curve.y);
+    rq = curve.z * rq * rq;
+    color * rq.max(br - threshold) / br.max(0.0001)
+}
+
+fn sample_13_tap(source: &Hdr, uv: Vec2) -> Vec3 {
+    let scale = source.texel_size();
+    let at = |x: f32, y: f32| source.sample(uv + Vec2::new(x, y) * scale);
+    let (a, b, c) = (at(-1., -1.), at(0., -1.), at(1., -1.));
+    let (d, e) = (at(-0.5, -0.5), at(0.5, -0.5));
+    let (f, g, h) = (at(-1., 0.), at(0., 0.), at(1., 0.));
+    let (i, j) = (at(-0.5, 0.5), at(0.5, 0.5));
+    let (k, l, m) = (at(-1., 1.), at(0., 1.), at(1., 1.));
+
+    let div = Vec2::new(0.5, 0.125) / 4.;
+    (d + e + i + j) * div.x
+        + (a + b + g + f) * div.y
+        + (b + c + h + g) * div.y
+        + (f + g + l + k) * div.y
+        + (g + h + m + l) * div.y
+}
+
+fn sample_3x3_tent(source: &Hdr, uv: Vec2, scale: f32) -> Vec3 {
+    let scale = source.texel_size() * scale;
+    let at = |x: f32, y: f32| source.sample(uv + Vec2::new(x, y) * scale);
+    (at(-1., -1.)
+        + at(0., -1.) * 2.
+        + at(1., -1.)
+        + at(-1., 0.) * 2.
+        + at(0., 0.) * 4.
+        + at(1., 0.) * 2.
+        + at(-1., 1.)
+        + at(0., 1.) * 2.
+        + at(1., 1.))
+        / 16.
+}
+
+// Linear filtering with the edges clamped, like the sampler of the GPU
+fn sample_bilinear(texels: &[Vec3], width: usize, height: usize, uv: Vec2) -> Vec3 {
+    let position = uv * Vec2::new(width as f32, height as f32) - Vec2::splat(0.5);
+    let base = position.floor();
+    let t = position - base;
+    let texel = |x: f32, y: f32| {
+        let x = (x as i64).clamp(0, width as i64 - 1) as usize;
+        let y = (y as i64).clamp(0, height as i64 - 1) as usize;
+        texels[y * width + x]
+    };
+    let top = texel(base.x, base.y).lerp(texel(base.x + 1., base.y), t.x);
+    let bottom = texel(base.x, base.y + 1.).lerp(texel(base.x + 1., base.y + 1.), t.x);
+    top.lerp(bottom, t.y)
+}
+
+fn remap(src_start: f32, src_end: f32, dst_start: f32, dst_end: f32, x: f32) -> f32 {
+    dst_start + (x - src_start) / (src_end - src_start) * (dst_end - dst_start)
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0., 1.) * 255.).round() as u8
+}
@@ -0,0 +1,223 @@
+//! Binary snapshot of a whole galaxy, to keep edited or evolved objects exactly.
+//!
+//! Layout, all numbers little endian:
+//! - magic `GLXS`, `u16` format version
+//! - `u32` length and json of the settings, as in a preset
+//! - `u64` seed, `f64` epoch in years, `u64` object count
+//! - per object a `u8` kind, a `u8` of flags (bit 0: on the bar) and the
+//!   `Star` fields as `f32`: `theta0`, `vel_theta`, `tilt_angle`, `a`, `b`, `temp`, `mag`
+//!
+//! Version 1 files have no flags byte, their objects are read as off the bar.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::density_wave::DensityWave;
+use super::galaxy_setting_component::GalaxySettings;
+use super::generator::GalaxyObject;
+use super::preset::{self, Preset};
+use super::star::{ObjectKind, Star};
+use super::validation::ConfigProblem;
+
+pub const SNAPSHOTS_DIR: &str = "snapshots";
+pub const SNAPSHOT_EXTENSION: &str = "snapshot";
+const MAGIC: &[u8; 4] = b"GLXS";
+const VERSION: u16 = 2;
+/// Flag of objects on a bar orbit
+const FLAG_BAR: u8 = 1;
+
+/// Settings, epoch and every object of a galaxy
+#[derive(Clone)]
+pub struct Snapshot {
+    pub galaxy_settings: GalaxySettings,
+    pub density_wave: DensityWave,
+    pub years: f64,
+    pub objects: Vec<GalaxyObject>,
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    NotASnapshot,
+    UnsupportedVersion(u16),
+    UnknownKind(u8),
+    /// Settings that would be reset on load, the objects were made with other ones
+    InvalidSettings(Vec<ConfigProblem>),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(err) => write!(f, "{}", err),
+            SnapshotError::Json(err) => write!(f, "invalid settings: {}", err),
+            SnapshotError::NotASnapshot => write!(f, "not a galaxy snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotError::UnknownKind(kind) => write!(f, "unknown object kind {}", kind),
+            SnapshotError::InvalidSettings(problems) => {
+                let fields: Vec<_> = problems
+                    .iter()
+                    .map(|problem| problem.field.as_str())
+                    .collect();
+                write!(f, "invalid settings: {}", fields.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(err: io::Error) -> Self {
+        SnapshotError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(err: serde_json::Error) -> Self {
+        SnapshotError::Json(err)
+    }
+}
+
+impl Snapshot {
+    pub fn write(&self, writer: &mut impl Write) -> Result<(), SnapshotError> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+
+        let settings = serde_json::to_vec(&Preset {
+            galaxy_settings: self.galaxy_settings.clone(),
+            density_wave: self.density_wave.clone(),
+            bookmarks: vec![],
+        })?;
+        writer.write_all(&(settings.len() as u32).to_le_bytes())?;
+        writer.write_all(&settings)?;
+
+        writer.write_all(&self.galaxy_settings.seed.to_le_bytes())?;
+        writer.write_all(&self.years.to_le_bytes())?;
+        writer.write_all(&(self.objects.len() as u64).to_le_bytes())?;
+
+        for object in &self.objects {
+            let star = &object.star;
+            let flags = if star.is_bar { FLAG_BAR } else { 0 };
+            writer.write_all(&[object.kind as u8, flags])?;
+            for value in [
+                star.theta0,
+                star.vel_theta,
+                star.tilt_angle,
+                star.a,
+                star.b,
+                star.temp,
+                star.mag,
+            ] {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a snapshot, the density wave is built and ready to use.
+    /// Settings are loaded exactly as saved, a snapshot with settings a preset
+    /// would reset is refused as they would not match the objects.
+    pub fn read(reader: &mut impl Read) -> Result<Snapshot, SnapshotError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        let version = u16::from_le_bytes(read_bytes(reader)?);
+        if !(1..=VERSION).contains(&version) {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let len = u32::from_le_bytes(read_bytes(reader)?) as usize;
+        let mut settings = vec![0; len];
+        reader.read_exact(&mut settings)?;
+        let value: serde_json::Value = serde_json::from_slice(&settings)?;
+        let (preset, problems) = Preset::from_value(&value);
+        if problems.iter().any(|problem| !problem.is_notice) {
+            return Err(SnapshotError::InvalidSettings(problems));
+        }
+
+        let mut galaxy_settings = preset.galaxy_settings;
+        galaxy_settings.seed = u64::from_le_bytes(read_bytes(reader)?);
+        let years = f64::from_le_bytes(read_bytes(reader)?);
+        let count = u64::from_le_bytes(read_bytes(reader)?) as usize;
+
+        // The count is not trusted for the allocation, a short file fails on read
+        let mut objects = Vec::with_capacity(count.min(galaxy_settings.get_count_all_objects()));
+        for _ in 0..count {
+            let [kind] = read_bytes(reader)?;
+            let kind = ObjectKind::ALL
+                .get(kind as usize)
+                .copied()
+                .ok_or(SnapshotError::UnknownKind(kind))?;
+            let [flags] = match version {
+                1 => [0],
+                _ => read_bytes(reader)?,
+            };
+            let mut values = [0.; 7];
+            for value in &mut values {
+                *value = f32::from_le_bytes(read_bytes(reader)?);
+            }
+            let [theta0, vel_theta, tilt_angle, a, b, temp, mag] = values;
+            let star = Star {
+                theta0,
+                vel_theta,
+                tilt_angle,
+                a,
+                b,
+                temp,
+                mag,
+                is_bar: flags & FLAG_BAR != 0,
+            };
+            objects.push(GalaxyObject { kind, star });
+        }
+
+        Ok(Snapshot {
+            galaxy_settings,
+            density_wave: preset.density_wave,
+            years,
+            objects,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SnapshotError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Snapshot, SnapshotError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Snapshot::read(&mut reader)
+    }
+}
+
+// Not `with_extension`, it would replace the end of names with a dot like `m.31`
+pub fn snapshot_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.{}", name, SNAPSHOT_EXTENSION))
+}
+
+/// Names of all snapshots in the directory, sorted
+pub fn list_snapshots(dir: &Path) -> Vec<String> {
+    preset::list_names(dir, SNAPSHOT_EXTENSION)
+}
+
+/// First free name of the form `snapshot_001`
+pub fn next_free_name(dir: &Path) -> String {
+    preset::next_free_name_with_prefix(dir, SNAPSHOT_EXTENSION, "snapshot")
+}
+
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
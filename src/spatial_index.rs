@@ -0,0 +1,174 @@
+//! Uniform grid over 2D positions, for picking and region queries without
+//! visiting every object.
+
+use bevy_math::{IVec2, Vec2};
+use std::collections::HashMap;
+
+/// Items bucketed by the grid cell of their position
+#[derive(Clone, Debug)]
+pub struct SpatialGrid<T> {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Vec2, T)>>,
+    len: usize,
+    min_cell: IVec2,
+    max_cell: IVec2,
+}
+
+impl<T: Copy> SpatialGrid<T> {
+    /// `cell_size` in world units, around the usual query radius works best
+    pub fn new(cell_size: f32) -> Self {
+        assert!(
+            cell_size.is_finite() && cell_size > 0.,
+            "cell size must be positive"
+        );
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+            len: 0,
+            min_cell: IVec2::splat(i32::MAX),
+            max_cell: IVec2::splat(i32::MIN),
+        }
+    }
+
+    pub fn from_points(cell_size: f32, points: impl IntoIterator<Item = (Vec2, T)>) -> Self {
+        let mut grid = SpatialGrid::new(cell_size);
+        for (position, item) in points {
+            grid.insert(position, item);
+        }
+        grid
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all items, the cells are kept to be refilled
+    pub fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        self.len = 0;
+        self.min_cell = IVec2::splat(i32::MAX);
+        self.max_cell = IVec2::splat(i32::MIN);
+    }
+
+    /// Positions that are not finite are ignored
+    pub fn insert(&mut self, position: Vec2, item: T) {
+        if !position.is_finite() {
+            return;
+        }
+        let cell = self.cell_of(position);
+        self.cells.entry(cell).or_default().push((position, item));
+        self.len += 1;
+        self.min_cell = self.min_cell.min(cell);
+        self.max_cell = self.max_cell.max(cell);
+    }
+
+    /// Items inside the rectangle, borders included
+    pub fn query_rect(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = (Vec2, T)> + '_ {
+        self.cells_between(min, max)
+            .filter(move |(position, _)| position.cmpge(min).all() && position.cmple(max).all())
+    }
+
+    /// Items at most `radius` away from `center`
+    pub fn query_radius(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Vec2, T)> + '_ {
+        let radius_squared = radius * radius;
+        self.cells_between(center - radius, center + radius)
+            .filter(move |(position, _)| position.distance_squared(center) <= radius_squared)
+    }
+
+    /// Closest item at most `max_distance` away, `f32::INFINITY` for no limit
+    pub fn nearest(&self, position: Vec2, max_distance: f32) -> Option<(Vec2, T)> {
+        if self.is_empty() || !position.is_finite() {
+            return None;
+        }
+
+        let center = self.cell_of(position);
+        // Rings closer than the occupied cells are empty, rings further out hold none
+        let offset_to = |cell: IVec2| {
+            (
+                (cell.x as i64 - center.x as i64),
+                (cell.y as i64 - center.y as i64),
+            )
+        };
+        let (min_x, min_y) = offset_to(self.min_cell);
+        let (max_x, max_y) = offset_to(self.max_cell);
+        let first_ring = [min_x, min_y, -max_x, -max_y, 0].into_iter().max().unwrap();
+        let last_ring = [-min_x, -min_y, max_x, max_y].into_iter().max().unwrap();
+
+        let mut best: Option<(f32, Vec2, T)> = None;
+        for ring in first_ring..=last_ring {
+            // Everything from this ring on is at least this far away
+            let ring_distance = (ring - 1).max(0) as f32 * self.cell_size;
+            if ring_distance > max_distance {
+                break;
+            }
+            if let Some((best_distance, _, _)) = best {
+                if best_distance <= ring_distance {
+                    break;
+                }
+            }
+
+            for cell in ring_cells(center, ring, (min_x, min_y), (max_x, max_y)) {
+                for &(item_position, item) in self.cells.get(&cell).into_iter().flatten() {
+                    let distance = item_position.distance(position);
+                    let is_closer =
+                        best.is_none_or(|(best_distance, _, _)| distance < best_distance);
+                    if distance <= max_distance && is_closer {
+                        best = Some((distance, item_position, item));
+                    }
+                }
+            }
+        }
+        best.map(|(_, item_position, item)| (item_position, item))
+    }
+
+    fn cell_of(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    fn cells_between(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = (Vec2, T)> + '_ {
+        // Clamp to the occupied cells, so huge rectangles don't visit empty ones
+        let min_cell = self.cell_of(min).max(self.min_cell);
+        let max_cell = self.cell_of(max).min(self.max_cell);
+        (min_cell.y..=max_cell.y)
+            .flat_map(move |y| (min_cell.x..=max_cell.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+}
+
+/// Cells exactly `ring` cells away from `center`, the centre itself for ring 0.
+/// Only the ones between the `min` and `max` offsets, where there can be items.
+fn ring_cells(
+    center: IVec2,
+    ring: i64,
+    min: (i64, i64),
+    max: (i64, i64),
+) -> impl Iterator<Item = IVec2> {
+    let sides = if ring == 0 {
+        vec![0]
+    } else {
+        vec![-ring, ring]
+    };
+    let rows = sides
+        .clone()
+        .into_iter()
+        .filter(move |y| (min.1..=max.1).contains(y))
+        .flat_map(move |y| ((-ring).max(min.0)..=ring.min(max.0)).map(move |x| (x, y)));
+    let columns = sides
+        .into_iter()
+        .filter(move |x| ring > 0 && (min.0..=max.0).contains(x))
+        .flat_map(move |x| ((1 - ring).max(min.1)..=(ring - 1).min(max.1)).map(move |y| (x, y)));
+    rows.chain(columns)
+        .map(move |(x, y)| IVec2::new((center.x as i64 + x) as i32, (center.y as i64 + y) as i32))
+}
@@ -0,0 +1,125 @@
+use bevy_math::Vec2;
+use std::f32;
+
+use super::galaxy_setting_component::GalaxySettings;
+
+#[derive(Debug, Default, Clone)]
+pub struct Star {
+    pub theta0: f32,     // initial angular position on the ellipse
+    pub vel_theta: f32,  // angular velocity
+    pub tilt_angle: f32, // tilt angle of the ellipse
+    pub a: f32,          // semi-minor axes
+    pub b: f32,          // semi-major axes
+    pub temp: f32,       // star temperature
+    pub mag: f32,        // brightness;
+    pub is_bar: bool,    // on a bar orbit, turns with the bar pattern
+}
+
+impl Star {
+    /// Position on the orbit at the given epoch in years
+    pub fn position(&self, galaxy_setting: &GalaxySettings, years: f64) -> Vec2 {
+        calculate_position(
+            galaxy_setting,
+            self.a,
+            self.b,
+            self.theta0,
+            self.vel_theta,
+            self.tilt_angle,
+            self.is_bar,
+            years,
+        )
+    }
+}
+
+// Type 0:star, 1:dust, 2 and 3: h2 regions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectKind {
+    Star,
+    Dust,
+    DustFilament,
+    H2,
+    H2Core,
+}
+
+impl ObjectKind {
+    pub const ALL: [ObjectKind; 5] = [
+        ObjectKind::Star,
+        ObjectKind::Dust,
+        ObjectKind::DustFilament,
+        ObjectKind::H2,
+        ObjectKind::H2Core,
+    ];
+
+    /// Seed of the random stream of this kind. Every kind has its own stream so
+    /// changing the count of one kind doesn't move the objects of the others.
+    pub fn stream_seed(&self, seed: u64) -> u64 {
+        const SALT: u64 = 0x9E37_79B9_7F4A_7C15;
+        seed ^ SALT.wrapping_mul(*self as u64 + 1)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObjectKind::Star => "star",
+            ObjectKind::Dust => "dust",
+            ObjectKind::DustFilament => "filament",
+            ObjectKind::H2 => "h2",
+            ObjectKind::H2Core => "h2_core",
+        }
+    }
+}
+
+/// Harvard spectral class of a star of the temperature in K
+pub fn spectral_class(temp: f32) -> char {
+    match temp {
+        t if t >= 30000. => 'O',
+        t if t >= 10000. => 'B',
+        t if t >= 7500. => 'A',
+        t if t >= 6000. => 'F',
+        t if t >= 5200. => 'G',
+        t if t >= 3700. => 'K',
+        _ => 'M',
+    }
+}
+
+const DEG_TO_RAD: f32 = 0.01745329251;
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_position(
+    galaxy_setting: &GalaxySettings,
+    a: f32,
+    b: f32,
+    theta0: f32,
+    vel_theta: f32,
+    tilt_angle: f32,
+    is_bar: bool,
+    years: f64,
+) -> Vec2 {
+    // Wrap the travelled angle in f64 so large epochs don't lose precision
+    let theta_actual = theta0 + ((vel_theta as f64 * years) % 360.) as f32;
+    // Orbits of the bar turn with its pattern speed, the others only follow their own
+    let pattern_angle = if is_bar {
+        galaxy_setting.get_pattern_angle(years)
+    } else {
+        0.
+    };
+    let beta = -(tilt_angle + pattern_angle);
+    let alpha = theta_actual * DEG_TO_RAD;
+    let cosalpha = alpha.cos();
+    let sinalpha = alpha.sin();
+    let cosbeta = beta.cos();
+    let sinbeta = beta.sin();
+
+    let mut pos = Vec2 {
+        x: (a * cosalpha * cosbeta - b * sinalpha * sinbeta),
+        y: (a * cosalpha * sinbeta + b * sinalpha * cosbeta),
+    };
+
+    // The perturbation shapes the density wave arms, other galaxies stay smooth
+    let has_density_wave_arms = galaxy_setting.has_arms() && !galaxy_setting.has_log_spiral_arms();
+    if has_density_wave_arms && galaxy_setting.pert_amp > 0 && galaxy_setting.pert_n > 0 {
+        pos.x += (a / galaxy_setting.pert_amp as f32)
+            * (alpha * 2.0 * galaxy_setting.pert_n as f32).sin();
+        pos.y += (a / galaxy_setting.pert_amp as f32)
+            * (alpha * 2.0 * galaxy_setting.pert_n as f32).cos();
+    }
+    pos
+}
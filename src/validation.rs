@@ -0,0 +1,653 @@
+//! Tolerant reading of settings files.
+//!
+//! Every field is read on its own, so a single bad value falls back to its
+//! default instead of discarding the whole file. Values that parse but would
+//! break generation (`bulge_radius > radius`, odd `steps`, ...) are reset too.
+//! Each fallback is reported as a `ConfigProblem` naming the field.
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::fmt;
+
+use super::bookmark::CameraBookmark;
+use super::density_wave::{DensityWave, Profile};
+use super::galaxy_setting_component::{GalaxySettings, Morphology};
+
+/// Read the listed fields one by one and report unknown ones.
+/// Fields after `optional` were added later, files without them keep the defaults.
+macro_rules! read_fields {
+    ($map:expr, $target:expr, $problems:expr, $($field:ident),+ $(; optional $($optional:ident),+)?) => {
+        $(read_field($map, stringify!($field), &mut $target.$field, &mut $problems);)+
+        $($(
+            if $map.contains_key(stringify!($optional)) {
+                read_field($map, stringify!($optional), &mut $target.$optional, &mut $problems);
+            }
+        )+)?
+        let known = [$(stringify!($field)),+ $($(, stringify!($optional))+)?];
+        for key in $map.keys().filter(|key| !known.contains(&key.as_str())) {
+            $problems.push(ConfigProblem::notice(key, "unknown field, ignored"));
+        }
+    };
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    /// Field name, empty when the problem concerns the whole file
+    pub field: String,
+    pub message: String,
+    /// Nothing read was changed, the problem is only worth knowing
+    pub is_notice: bool,
+}
+
+impl ConfigProblem {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        ConfigProblem {
+            field: field.to_string(),
+            message: message.into(),
+            is_notice: false,
+        }
+    }
+
+    pub fn notice(field: &str, message: impl Into<String>) -> Self {
+        ConfigProblem {
+            is_notice: true,
+            ..ConfigProblem::new(field, message)
+        }
+    }
+
+    /// Prefix the field with the name of the section it was read from
+    pub fn in_section(self, section: &str) -> Self {
+        let field = if self.field.is_empty() {
+            section.to_string()
+        } else {
+            format!("{}.{}", section, self.field)
+        };
+        ConfigProblem { field, ..self }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.field, self.message)
+        }
+    }
+}
+
+/// Read `GalaxySettings` from json text, see `galaxy_settings_from_value`
+pub fn galaxy_settings_from_str(json: &str) -> (GalaxySettings, Vec<ConfigProblem>) {
+    match serde_json::from_str::<Value>(json) {
+        Ok(value) => galaxy_settings_from_value(&value),
+        Err(err) => (
+            GalaxySettings::default(),
+            vec![ConfigProblem::new(
+                "",
+                format!("invalid json ({}), using defaults", err),
+            )],
+        ),
+    }
+}
+
+/// Read `GalaxySettings` field by field and validate the result
+pub fn galaxy_settings_from_value(value: &Value) -> (GalaxySettings, Vec<ConfigProblem>) {
+    let mut settings = GalaxySettings::default();
+    let mut problems = vec![];
+
+    if let Some(map) = as_object(value, &mut problems) {
+        read_fields!(
+            map,
+            settings,
+            problems,
+            radius,
+            bulge_radius,
+            far_field_radius,
+            angular_offset,
+            inner_excentricity,
+            outter_excentricity,
+            ellipse_disturbances,
+            ellipse_disturbances_damping,
+            count_stars,
+            count_dusts,
+            count_dusts_filaments,
+            count_h2,
+            count_h2_core,
+            has_dark_matter,
+            base_temp,
+            dust_render_size,
+            pert_n,
+            pert_amp,
+            seed;
+            optional bar_length,
+            bar_axis_ratio,
+            bar_pattern_speed,
+            bar_star_fraction,
+            morphology,
+            elliptical_class,
+            sersic_index,
+            arm_model,
+            arm_count,
+            arm_pitch,
+            arm_width
+        );
+    }
+
+    problems.extend(validate_galaxy_settings(&mut settings));
+    (settings, problems)
+}
+
+/// Read `DensityWave` from json text, see `density_wave_from_value`
+pub fn density_wave_from_str(
+    json: &str,
+    galaxy: &GalaxySettings,
+) -> (DensityWave, Vec<ConfigProblem>) {
+    match serde_json::from_str::<Value>(json) {
+        Ok(value) => density_wave_from_value(&value, galaxy),
+        Err(err) => {
+            let mut wave = DensityWave::for_galaxy(galaxy);
+            wave.build();
+            (
+                wave,
+                vec![ConfigProblem::new(
+                    "",
+                    format!("invalid json ({}), using defaults", err),
+                )],
+            )
+        }
+    }
+}
+
+/// Read `DensityWave` field by field, validate and build it.
+/// Missing or invalid fields fall back to the wave scaled to `galaxy`.
+pub fn density_wave_from_value(
+    value: &Value,
+    galaxy: &GalaxySettings,
+) -> (DensityWave, Vec<ConfigProblem>) {
+    let mut wave = DensityWave::for_galaxy(galaxy);
+    let mut problems = vec![];
+
+    if let Some(map) = as_object(value, &mut problems) {
+        read_fields!(
+            map,
+            wave,
+            problems,
+            min,
+            max,
+            steps,
+            i0,
+            k,
+            a,
+            bulge_radius;
+            optional profile
+        );
+    }
+
+    problems.extend(validate_density_wave(&mut wave, galaxy));
+    (wave, problems)
+}
+
+/// Reset fields that would break generation, returns what was reset
+pub fn validate_galaxy_settings(settings: &mut GalaxySettings) -> Vec<ConfigProblem> {
+    let default = GalaxySettings::default();
+    let mut problems = vec![];
+
+    let is_valid = settings.radius.is_finite() && settings.radius > 0.;
+    check(
+        &mut problems,
+        "radius",
+        &mut settings.radius,
+        is_valid,
+        default.radius,
+        "must be positive",
+    );
+
+    let radius = settings.radius;
+    let is_valid = settings.bulge_radius.is_finite()
+        && settings.bulge_radius >= 0.
+        && settings.bulge_radius < radius;
+    let fallback = if default.bulge_radius < radius {
+        default.bulge_radius
+    } else {
+        radius * default.bulge_radius / default.radius
+    };
+    check(
+        &mut problems,
+        "bulge_radius",
+        &mut settings.bulge_radius,
+        is_valid,
+        fallback,
+        "must be at least 0 and smaller than radius",
+    );
+
+    let is_valid = settings.far_field_radius.is_finite() && settings.far_field_radius > radius;
+    let fallback = if default.far_field_radius > radius {
+        default.far_field_radius
+    } else {
+        radius * default.far_field_radius / default.radius
+    };
+    check(
+        &mut problems,
+        "far_field_radius",
+        &mut settings.far_field_radius,
+        is_valid,
+        fallback,
+        "must be larger than radius",
+    );
+
+    let is_valid = settings.angular_offset.is_finite();
+    check(
+        &mut problems,
+        "angular_offset",
+        &mut settings.angular_offset,
+        is_valid,
+        default.angular_offset,
+        "must be a number",
+    );
+
+    let is_valid = settings.inner_excentricity.is_finite() && settings.inner_excentricity > 0.;
+    check(
+        &mut problems,
+        "inner_excentricity",
+        &mut settings.inner_excentricity,
+        is_valid,
+        default.inner_excentricity,
+        "must be positive",
+    );
+
+    let is_valid = settings.outter_excentricity.is_finite() && settings.outter_excentricity > 0.;
+    check(
+        &mut problems,
+        "outter_excentricity",
+        &mut settings.outter_excentricity,
+        is_valid,
+        default.outter_excentricity,
+        "must be positive",
+    );
+
+    let is_valid = settings.ellipse_disturbances >= 0;
+    check(
+        &mut problems,
+        "ellipse_disturbances",
+        &mut settings.ellipse_disturbances,
+        is_valid,
+        default.ellipse_disturbances,
+        "must be at least 0",
+    );
+
+    // At 1 the disturbances shrink some ellipses to a point
+    let is_valid = settings.ellipse_disturbances_damping >= 2;
+    check(
+        &mut problems,
+        "ellipse_disturbances_damping",
+        &mut settings.ellipse_disturbances_damping,
+        is_valid,
+        default.ellipse_disturbances_damping,
+        "must be at least 2",
+    );
+
+    let is_valid = settings.base_temp.is_finite() && settings.base_temp > 0.;
+    check(
+        &mut problems,
+        "base_temp",
+        &mut settings.base_temp,
+        is_valid,
+        default.base_temp,
+        "must be positive",
+    );
+
+    let is_valid = settings.dust_render_size.is_finite() && settings.dust_render_size > 0.;
+    check(
+        &mut problems,
+        "dust_render_size",
+        &mut settings.dust_render_size,
+        is_valid,
+        default.dust_render_size,
+        "must be positive",
+    );
+
+    let is_valid = settings.pert_n >= 0;
+    check(
+        &mut problems,
+        "pert_n",
+        &mut settings.pert_n,
+        is_valid,
+        default.pert_n,
+        "must be at least 0",
+    );
+
+    let is_valid = settings.pert_amp >= 0;
+    check(
+        &mut problems,
+        "pert_amp",
+        &mut settings.pert_amp,
+        is_valid,
+        default.pert_amp,
+        "must be at least 0",
+    );
+
+    let is_valid = settings.bar_length.is_finite()
+        && settings.bar_length >= 0.
+        && settings.bar_length < settings.radius;
+    check(
+        &mut problems,
+        "bar_length",
+        &mut settings.bar_length,
+        is_valid,
+        default.bar_length,
+        "must be at least 0 and smaller than radius",
+    );
+
+    let is_valid = settings.bar_axis_ratio > 0. && settings.bar_axis_ratio <= 1.;
+    check(
+        &mut problems,
+        "bar_axis_ratio",
+        &mut settings.bar_axis_ratio,
+        is_valid,
+        default.bar_axis_ratio,
+        "must be above 0 and at most 1",
+    );
+
+    let is_valid = settings.bar_pattern_speed.is_finite();
+    check(
+        &mut problems,
+        "bar_pattern_speed",
+        &mut settings.bar_pattern_speed,
+        is_valid,
+        default.bar_pattern_speed,
+        "must be a number",
+    );
+
+    let is_valid = (0. ..=1.).contains(&settings.bar_star_fraction);
+    check(
+        &mut problems,
+        "bar_star_fraction",
+        &mut settings.bar_star_fraction,
+        is_valid,
+        default.bar_star_fraction,
+        "must be between 0 and 1",
+    );
+
+    let is_valid = settings.elliptical_class <= 7;
+    check(
+        &mut problems,
+        "elliptical_class",
+        &mut settings.elliptical_class,
+        is_valid,
+        default.elliptical_class,
+        "must be between 0 and 7",
+    );
+
+    // The radii are drawn from a gamma distribution of shape 2n, which needs 2n >= 1
+    let is_valid = (0.5..=10.).contains(&settings.sersic_index);
+    check(
+        &mut problems,
+        "sersic_index",
+        &mut settings.sersic_index,
+        is_valid,
+        default.sersic_index,
+        "must be between 0.5 and 10",
+    );
+
+    let is_valid = (2..=6).contains(&settings.arm_count);
+    check(
+        &mut problems,
+        "arm_count",
+        &mut settings.arm_count,
+        is_valid,
+        default.arm_count,
+        "must be between 2 and 6",
+    );
+
+    let is_valid = settings.arm_pitch > 0. && settings.arm_pitch < 90.;
+    check(
+        &mut problems,
+        "arm_pitch",
+        &mut settings.arm_pitch,
+        is_valid,
+        default.arm_pitch,
+        "must be above 0 and below 90",
+    );
+
+    let is_valid = settings.arm_width.is_finite() && settings.arm_width >= 0.;
+    check(
+        &mut problems,
+        "arm_width",
+        &mut settings.arm_width,
+        is_valid,
+        default.arm_width,
+        "must be at least 0",
+    );
+
+    problems
+}
+
+/// Reset fields that would break the distribution and build the wave.
+/// Falls back to the whole default wave if the built tables are still not finite.
+pub fn validate_density_wave(
+    wave: &mut DensityWave,
+    galaxy: &GalaxySettings,
+) -> Vec<ConfigProblem> {
+    let default = DensityWave::for_galaxy(galaxy);
+    let mut problems = vec![];
+
+    // Simpson integration needs an even number of steps
+    let is_valid = wave.steps > 0 && wave.steps % 2 == 0;
+    check(
+        &mut problems,
+        "steps",
+        &mut wave.steps,
+        is_valid,
+        default.steps,
+        "must be a positive even number",
+    );
+
+    let is_valid = wave.min.is_finite() && wave.min >= 0.;
+    check(
+        &mut problems,
+        "min",
+        &mut wave.min,
+        is_valid,
+        default.min,
+        "must be at least 0",
+    );
+
+    let min = wave.min;
+    let is_valid = wave.max.is_finite() && wave.max > min;
+    let fallback = if default.max > min {
+        default.max
+    } else {
+        min + default.max
+    };
+    check(
+        &mut problems,
+        "max",
+        &mut wave.max,
+        is_valid,
+        fallback,
+        "must be larger than min",
+    );
+
+    let is_valid = wave.i0.is_finite() && wave.i0 > 0.;
+    check(
+        &mut problems,
+        "i0",
+        &mut wave.i0,
+        is_valid,
+        default.i0,
+        "must be positive",
+    );
+
+    let is_valid = wave.k.is_finite() && wave.k >= 0.;
+    check(
+        &mut problems,
+        "k",
+        &mut wave.k,
+        is_valid,
+        default.k,
+        "must be at least 0",
+    );
+
+    let is_valid = wave.a.is_finite() && wave.a > 0.;
+    check(
+        &mut problems,
+        "a",
+        &mut wave.a,
+        is_valid,
+        default.a,
+        "must be positive",
+    );
+
+    let is_valid = wave.bulge_radius.is_finite() && wave.bulge_radius >= 0.;
+    check(
+        &mut problems,
+        "bulge_radius",
+        &mut wave.bulge_radius,
+        is_valid,
+        default.bulge_radius,
+        "must be at least 0",
+    );
+
+    let rule = profile_rule(&wave.profile);
+    check(
+        &mut problems,
+        "profile",
+        &mut wave.profile,
+        rule.is_none(),
+        default.profile.clone(),
+        rule.unwrap_or_default(),
+    );
+
+    // Kept for when the galaxy becomes a spiral again
+    if galaxy.morphology == Morphology::Elliptical && wave.profile != Profile::BulgeDisc {
+        problems.push(ConfigProblem::notice(
+            "profile",
+            "ignored by ellipticals, they follow sersic_index",
+        ));
+    }
+
+    wave.build();
+    let is_finite = wave
+        .y2
+        .iter()
+        .chain(&wave.m2)
+        .all(|value| value.is_finite());
+    if !is_finite {
+        problems.push(ConfigProblem::new(
+            "",
+            "distribution is not finite, using the default wave",
+        ));
+        *wave = default;
+        wave.build();
+    }
+
+    problems
+}
+
+/// Rule the parameters of the profile break, if any
+fn profile_rule(profile: &Profile) -> Option<&'static str> {
+    let positive = |values: &[f32]| values.iter().all(|value| value.is_finite() && *value > 0.);
+    match *profile {
+        Profile::BulgeDisc => None,
+        Profile::Sersic {
+            n,
+            effective_radius,
+        } => (!(0.2..=10.).contains(&n) || !positive(&[effective_radius]))
+            .then_some("sersic needs n between 0.2 and 10 and a positive effective_radius"),
+        Profile::Exponential { scale_length } => {
+            (!positive(&[scale_length])).then_some("exponential needs a positive scale_length")
+        }
+        Profile::King {
+            core_radius,
+            tidal_radius,
+        } => (!positive(&[core_radius, tidal_radius]) || tidal_radius <= core_radius)
+            .then_some("king needs a positive core_radius and a larger tidal_radius"),
+        Profile::DoubleExponential {
+            inner_scale_length,
+            outer_scale_length,
+            break_radius,
+        } => (!positive(&[inner_scale_length, outer_scale_length, break_radius]))
+            .then_some("double_exponential needs positive scale lengths and break_radius"),
+    }
+}
+
+/// Read a list of camera bookmarks, entries that can't be used are skipped
+pub fn bookmarks_from_value(value: &Value) -> (Vec<CameraBookmark>, Vec<ConfigProblem>) {
+    let mut problems = vec![];
+    let Some(entries) = value.as_array() else {
+        problems.push(ConfigProblem::new("", "expected a json array, ignored"));
+        return (vec![], problems);
+    };
+
+    let mut bookmarks = vec![];
+    for (i, entry) in entries.iter().enumerate() {
+        let field = i.to_string();
+        match serde_json::from_value::<CameraBookmark>(entry.clone()) {
+            Ok(bookmark) if bookmark.is_valid() => bookmarks.push(bookmark),
+            Ok(bookmark) => problems.push(ConfigProblem::new(
+                &field,
+                format!(
+                    "{} needs a finite center and a positive scale, ignored",
+                    bookmark.name
+                ),
+            )),
+            Err(err) => problems.push(ConfigProblem::new(
+                &field,
+                format!("{} ({}), ignored", err, entry),
+            )),
+        }
+    }
+    (bookmarks, problems)
+}
+
+fn as_object<'a>(
+    value: &'a Value,
+    problems: &mut Vec<ConfigProblem>,
+) -> Option<&'a Map<String, Value>> {
+    let map = value.as_object();
+    if map.is_none() {
+        problems.push(ConfigProblem::new(
+            "",
+            "expected a json object, using defaults",
+        ));
+    }
+    map
+}
+
+fn read_field<T: DeserializeOwned + fmt::Debug>(
+    map: &Map<String, Value>,
+    field: &str,
+    target: &mut T,
+    problems: &mut Vec<ConfigProblem>,
+) {
+    match map.get(field) {
+        Some(value) => match serde_json::from_value(value.clone()) {
+            Ok(parsed) => *target = parsed,
+            Err(err) => problems.push(ConfigProblem::new(
+                field,
+                format!("{} ({}), using {:?}", err, value, target),
+            )),
+        },
+        None => problems.push(ConfigProblem::new(
+            field,
+            format!("missing, using {:?}", target),
+        )),
+    }
+}
+
+fn check<T: fmt::Debug>(
+    problems: &mut Vec<ConfigProblem>,
+    field: &str,
+    value: &mut T,
+    is_valid: bool,
+    fallback: T,
+    rule: &str,
+) {
+    if !is_valid {
+        problems.push(ConfigProblem::new(
+            field,
+            format!("{}, got {:?}, using {:?}", rule, value, fallback),
+        ));
+        *value = fallback;
+    }
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{fields, generate, settings};
+use galaxy::galaxy_setting_component::{ArmModel, GalaxySettings};
+use galaxy::generator::GalaxyObject;
+use galaxy::star::ObjectKind;
+use galaxy::validation;
+use std::f32::consts::{PI, TAU};
+
+fn log_spiral(arm_count: u8) -> GalaxySettings {
+    GalaxySettings {
+        arm_model: ArmModel::LogSpiral,
+        arm_count,
+        arm_pitch: 20.,
+        arm_width: 0.,
+        ..settings()
+    }
+}
+
+/// Index of the arm the position is on, if it is on one
+fn arm_of(settings: &GalaxySettings, object: &GalaxyObject) -> Option<u8> {
+    let position = object.star.position(settings, 0.);
+    let rad = position.length();
+    (0..settings.arm_count).find(|arm| {
+        let offset =
+            (position.y.atan2(position.x) - settings.get_arm_angle(*arm, rad)).rem_euclid(TAU);
+        offset.min(TAU - offset) * rad < 1.
+    })
+}
+
+#[test]
+fn stars_outside_the_bulge_are_on_the_arms() {
+    for arm_count in [2, 3, 6] {
+        let settings = log_spiral(arm_count);
+        let mut per_arm = vec![0; arm_count as usize];
+        for object in generate(&settings) {
+            if object.star.a <= settings.get_arm_start_radius() {
+                continue;
+            }
+            let arm = arm_of(&settings, &object)
+                .unwrap_or_else(|| panic!("{:?} is not on an arm", object));
+            // A whole filament is on one arm, only stars are spread evenly
+            if object.kind == ObjectKind::Star {
+                per_arm[arm as usize] += 1;
+            }
+        }
+
+        let total: usize = per_arm.iter().sum();
+        for count in per_arm {
+            let share = count as f32 * arm_count as f32 / total as f32;
+            assert!((share - 1.).abs() < 0.2, "{} arms: {}", arm_count, share);
+        }
+    }
+}
+
+#[test]
+fn arms_wind_by_the_pitch_angle() {
+    let settings = log_spiral(2);
+    let start = settings.get_arm_start_radius();
+    // One turn further out the radius grows by exp(2 pi tan(pitch))
+    let turn = (TAU * settings.arm_pitch.to_radians().tan()).exp();
+    let inner = settings.get_arm_angle(0, start);
+    let outer = settings.get_arm_angle(0, start * turn);
+    assert!((inner - outer - TAU).abs() < 1e-4);
+    assert!((settings.get_arm_angle(1, start) - inner - PI).abs() < 1e-6);
+}
+
+#[test]
+fn arm_orbits_are_circles_without_perturbation() {
+    let settings = log_spiral(2);
+    let objects = generate(&settings);
+    // Inside the arm start the H2 regions are still on the ellipses of the bulge
+    for object in objects.iter().filter(|object| {
+        object.kind == ObjectKind::H2 && object.star.a > settings.get_arm_start_radius()
+    }) {
+        let position = object.star.position(&settings, 1e8);
+        assert!((position.length() - object.star.a).abs() < object.star.a * 1e-4);
+    }
+}
+
+#[test]
+fn invalid_arm_settings_are_reset() {
+    let mut value = serde_json::to_value(GalaxySettings::default()).unwrap();
+    assert_eq!(value["arm_model"], "density_wave");
+    value["arm_model"] = serde_json::json!("log_spiral");
+    value["arm_count"] = serde_json::json!(7);
+    value["arm_pitch"] = serde_json::json!(90.);
+    value["arm_width"] = serde_json::json!(-1.);
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    assert_eq!(fields(&problems), ["arm_count", "arm_pitch", "arm_width"]);
+    assert!(settings.has_log_spiral_arms());
+    assert_eq!(settings.arm_count, 2);
+}
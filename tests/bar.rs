@@ -0,0 +1,87 @@
+mod common;
+
+use bevy_math::Vec2;
+use common::{fields, generate, settings};
+use galaxy::galaxy_setting_component::GalaxySettings;
+use galaxy::star::ObjectKind;
+use galaxy::validation;
+
+#[test]
+fn bar_stars_are_elongated_orbits_inside_the_bar() {
+    let settings = GalaxySettings {
+        bar_length: 5000.,
+        bar_star_fraction: 0.3,
+        ..settings()
+    };
+    let objects = generate(&settings);
+    let bar_stars: Vec<_> = objects.iter().filter(|object| object.star.is_bar).collect();
+
+    let fraction = bar_stars.len() as f32 / settings.count_stars as f32;
+    assert!((fraction - 0.3).abs() < 0.05, "{}", fraction);
+    for object in bar_stars {
+        let star = &object.star;
+        assert_eq!(object.kind, ObjectKind::Star);
+        assert!(star.a <= settings.bar_length);
+        assert!(star.b <= star.a * settings.bar_axis_ratio * 1.25);
+        assert!((star.tilt_angle - settings.get_bar_angle()).abs() <= 0.025);
+        assert!(star.vel_theta.is_finite());
+    }
+}
+
+#[test]
+fn only_the_bar_turns_with_the_pattern_speed() {
+    let settings = GalaxySettings {
+        bar_length: 5000.,
+        bar_pattern_speed: 40.,
+        // The perturbation is added after the turn
+        pert_n: 0,
+        ..settings()
+    };
+    let still = GalaxySettings {
+        bar_pattern_speed: 0.,
+        ..settings.clone()
+    };
+    let years = 1e8;
+    let turn = settings.get_pattern_angle(years);
+    assert!(turn.abs() > 0.1);
+
+    let objects = generate(&settings);
+    assert!(objects.iter().any(|object| object.star.is_bar));
+    for object in &objects {
+        let moved = object.star.position(&settings, years);
+        let unmoved = object.star.position(&still, years);
+        if object.star.is_bar {
+            let expected = Vec2::from_angle(-turn).rotate(unmoved);
+            assert!(moved.distance(expected) <= 1e-2 * unmoved.length().max(1.));
+        } else {
+            assert_eq!(moved, unmoved, "{:?}", object);
+        }
+    }
+}
+
+#[test]
+fn orbits_inside_the_bar_are_aligned_with_it() {
+    let settings = GalaxySettings {
+        bar_length: 5000.,
+        ..settings()
+    };
+    let bar_angle = settings.get_bar_angle();
+    assert_eq!(settings.get_angular_offset(100.), bar_angle);
+    assert_eq!(settings.get_angular_offset(4999.), bar_angle);
+    // Arms continue from the bar ends like they would without a bar
+    assert_eq!(
+        settings.get_angular_offset(9000.),
+        9000. * settings.angular_offset
+    );
+    assert!(settings.get_pattern_angle(1e8) != 0.);
+}
+
+#[test]
+fn bars_longer_than_the_galaxy_are_reset() {
+    let mut value = serde_json::to_value(GalaxySettings::default()).unwrap();
+    value["bar_length"] = serde_json::json!(50000.);
+    value["bar_axis_ratio"] = serde_json::json!(0.);
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    assert_eq!(fields(&problems), ["bar_length", "bar_axis_ratio"]);
+    assert_eq!(settings.bar_length, 0.);
+}
@@ -0,0 +1,70 @@
+use bevy_math::Vec2;
+use galaxy::bookmark::{self, CameraBookmark};
+use galaxy::density_wave::DensityWave;
+use galaxy::galaxy_setting_component::GalaxySettings;
+use galaxy::preset::Preset;
+use serde_json::json;
+
+fn preset_with(bookmarks: Vec<CameraBookmark>) -> Preset {
+    let galaxy_settings = GalaxySettings::default();
+    let mut density_wave = DensityWave::for_galaxy(&galaxy_settings);
+    density_wave.build();
+    Preset {
+        galaxy_settings,
+        density_wave,
+        bookmarks,
+    }
+}
+
+#[test]
+fn bookmarks_round_trip_through_a_preset() {
+    let bookmarks = vec![
+        CameraBookmark::new("bulge", Vec2::ZERO, 2.5),
+        CameraBookmark::new("far arm", Vec2::new(-12000.5, 3400.25), 40.),
+    ];
+    let value = serde_json::to_value(preset_with(bookmarks.clone())).unwrap();
+
+    let (preset, problems) = Preset::from_value(&value);
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(preset.bookmarks, bookmarks);
+}
+
+#[test]
+fn presets_without_bookmarks_have_no_problems() {
+    let mut value = serde_json::to_value(preset_with(vec![])).unwrap();
+    assert!(value.get("bookmarks").is_none());
+    value.as_object_mut().unwrap().remove("bookmarks");
+
+    let (preset, problems) = Preset::from_value(&value);
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert!(preset.bookmarks.is_empty());
+}
+
+#[test]
+fn unusable_bookmarks_are_skipped_and_reported() {
+    let mut value = serde_json::to_value(preset_with(vec![])).unwrap();
+    value["bookmarks"] = json!([
+        {"name": "ok", "x": 1.0, "y": 2.0, "scale": 3.0},
+        {"name": "flat", "x": 1.0, "y": 2.0, "scale": 0.0},
+        {"name": "no center", "scale": 3.0},
+    ]);
+
+    let (preset, problems) = Preset::from_value(&value);
+    assert_eq!(preset.bookmarks.len(), 1);
+    assert_eq!(preset.bookmarks[0].name, "ok");
+    let fields: Vec<_> = problems
+        .iter()
+        .map(|problem| problem.field.as_str())
+        .collect();
+    assert_eq!(fields, ["bookmarks.1", "bookmarks.2"]);
+}
+
+#[test]
+fn new_names_are_not_taken() {
+    let bookmarks = vec![
+        CameraBookmark::new("view_1", Vec2::ZERO, 1.),
+        CameraBookmark::new("view_3", Vec2::ZERO, 1.),
+    ];
+    assert_eq!(bookmark::next_free_name(&bookmarks), "view_2");
+    assert_eq!(bookmark::next_free_name(&[]), "view_1");
+}
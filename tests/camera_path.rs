@@ -0,0 +1,89 @@
+use bevy_math::Vec2;
+use galaxy::camera_path::{self, CameraPath, CameraPathError, Keyframe};
+
+fn path() -> CameraPath {
+    CameraPath {
+        keyframes: vec![
+            Keyframe::new(0., Vec2::new(0., 0.), 70., 0.),
+            Keyframe::new(2., Vec2::new(1000., -500.), 7., 1e6),
+            Keyframe::new(5., Vec2::new(3000., 0.), 0.5, 4e6),
+            Keyframe::new(6., Vec2::new(3000., 2000.), 1., 4e6),
+        ],
+    }
+}
+
+#[test]
+fn samples_pass_through_the_keyframes() {
+    let path = path();
+    for keyframe in &path.keyframes {
+        let state = path.sample(keyframe.time).unwrap();
+        assert!(state.center.distance(keyframe.center()) < 1e-2);
+        assert!((state.scale / keyframe.scale - 1.).abs() < 1e-4);
+        assert_eq!(state.years, keyframe.years);
+    }
+}
+
+#[test]
+fn samples_are_held_before_and_after_the_path() {
+    let path = path();
+    assert_eq!(path.duration(), 6.);
+    assert_eq!(path.sample(-1.), path.sample(0.));
+    assert_eq!(path.sample(100.), path.sample(6.));
+    assert_eq!(CameraPath::default().sample(0.), None);
+}
+
+#[test]
+fn the_path_starts_and_ends_at_rest() {
+    let path = path();
+    let start = path.sample(0.).unwrap().center;
+    let end = path.sample(6.).unwrap().center;
+    let dt = 1e-3;
+    assert!(path.sample(dt).unwrap().center.distance(start) < 1.);
+    assert!(path.sample(6. - dt).unwrap().center.distance(end) < 1.);
+}
+
+#[test]
+fn epoch_moves_linearly_between_keyframes() {
+    let state = path().sample(3.5).unwrap();
+    assert!((state.years - 2.5e6).abs() < 1.);
+    assert!(state.scale > 0.5 && state.scale < 7.);
+}
+
+#[test]
+fn keyframes_at_the_same_time_replace_the_last() {
+    let mut path = CameraPath::default();
+    path.push(Keyframe::new(0., Vec2::ZERO, 1., 0.));
+    path.push(Keyframe::new(1., Vec2::ZERO, 1., 0.));
+    path.push(Keyframe::new(1., Vec2::ONE, 2., 5.));
+    assert_eq!(path.len(), 2);
+    assert_eq!(path.keyframes[1], Keyframe::new(1., Vec2::ONE, 2., 5.));
+}
+
+#[test]
+fn round_trips_through_a_file() {
+    let dir = std::env::temp_dir().join(format!("galaxy_camera_path_{}", std::process::id()));
+    let file = camera_path::camera_path_file(&dir, "path.2");
+    assert_eq!(file.file_name().unwrap(), "path.2.json");
+    path().save(&file).unwrap();
+    let loaded = CameraPath::load(&file).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(loaded, path());
+}
+
+#[test]
+fn unordered_or_flat_keyframes_are_rejected() {
+    let mut unordered = path();
+    unordered.keyframes.swap(1, 2);
+    assert!(unordered.validate().is_err());
+
+    let mut flat = path();
+    flat.keyframes[0].scale = 0.;
+    assert!(flat.validate().is_err());
+
+    let dir = std::env::temp_dir().join(format!("galaxy_bad_path_{}", std::process::id()));
+    let file = dir.join("path.json");
+    unordered.save(&file).unwrap();
+    let result = CameraPath::load(&file);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(result, Err(CameraPathError::Invalid(_))));
+}
@@ -0,0 +1,33 @@
+// Every test file uses only part of it
+#![allow(dead_code)]
+
+use galaxy::density_wave::DensityWave;
+use galaxy::galaxy_setting_component::GalaxySettings;
+use galaxy::generator::{self, GalaxyObject};
+use galaxy::validation::ConfigProblem;
+
+/// Small spiral galaxy, enough stars for statistics and a few of every other kind
+pub fn settings() -> GalaxySettings {
+    GalaxySettings {
+        count_stars: 4000,
+        count_dusts: 500,
+        count_dusts_filaments: 500,
+        count_h2: 50,
+        count_h2_core: 50,
+        ..Default::default()
+    }
+}
+
+pub fn generate(settings: &GalaxySettings) -> Vec<GalaxyObject> {
+    let mut density_wave = DensityWave::for_galaxy(settings);
+    density_wave.build();
+    generator::generate(settings, &density_wave)
+}
+
+/// Fields the problems are about, in the order they were found
+pub fn fields(problems: &[ConfigProblem]) -> Vec<&str> {
+    problems
+        .iter()
+        .map(|problem| problem.field.as_str())
+        .collect()
+}
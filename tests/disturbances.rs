@@ -0,0 +1,115 @@
+mod common;
+
+use common::{generate, settings};
+use galaxy::galaxy_setting_component::{ArmModel, GalaxySettings, Morphology};
+use galaxy::generator::GalaxyObject;
+use galaxy::star::ObjectKind;
+
+fn star_bits(object: &GalaxyObject) -> [u32; 4] {
+    let star = &object.star;
+    [star.theta0, star.tilt_angle, star.a, star.b].map(f32::to_bits)
+}
+
+#[test]
+fn disturbances_swell_and_turn_every_disc_ellipse() {
+    let plain = settings();
+    let disturbed = GalaxySettings {
+        ellipse_disturbances: 3,
+        ellipse_disturbances_damping: 10,
+        ..plain.clone()
+    };
+    let (plain, disturbed_objects) = (generate(&plain), generate(&disturbed));
+    assert_eq!(plain.len(), disturbed_objects.len());
+
+    // Only the density wave disc of stars and dust is disturbed
+    let pairs: Vec<_> = plain
+        .iter()
+        .zip(&disturbed_objects)
+        .skip(1)
+        .filter(|(object, _)| matches!(object.kind, ObjectKind::Star | ObjectKind::Dust))
+        .collect();
+    let mut changed = 0;
+    for &(before, after) in &pairs {
+        let (scale, tilt) = disturbed.get_ellipse_disturbance(before.star.a);
+        assert!((0.9..=1.1).contains(&scale));
+        assert!((after.star.a - before.star.a * scale).abs() <= before.star.a * 1e-6);
+        assert!((after.star.b - before.star.b * scale).abs() <= before.star.b * 1e-6);
+        assert!((after.star.tilt_angle - before.star.tilt_angle - tilt).abs() < 1e-4);
+        if after.star.a != before.star.a {
+            changed += 1;
+        }
+    }
+    assert!(changed > pairs.len() * 9 / 10);
+
+    for (before, after) in plain.iter().zip(&disturbed_objects) {
+        if matches!(
+            before.kind,
+            ObjectKind::DustFilament | ObjectKind::H2 | ObjectKind::H2Core
+        ) {
+            assert_eq!(star_bits(before), star_bits(after));
+        }
+    }
+    assert_eq!(star_bits(&plain[0]), star_bits(&disturbed_objects[0]));
+}
+
+#[test]
+fn arm_and_bar_stars_are_not_disturbed() {
+    let plain = GalaxySettings {
+        arm_model: ArmModel::LogSpiral,
+        bar_length: 3000.,
+        bar_star_fraction: 0.3,
+        ..settings()
+    };
+    let disturbed = GalaxySettings {
+        ellipse_disturbances: 3,
+        ellipse_disturbances_damping: 10,
+        ..plain.clone()
+    };
+    let (objects, disturbed_objects) = (generate(&plain), generate(&disturbed));
+    assert_eq!(objects.len(), disturbed_objects.len());
+
+    let (mut bar, mut arm) = (0, 0);
+    for (before, after) in objects.iter().zip(&disturbed_objects) {
+        let is_on_arm = before.star.a > plain.get_arm_start_radius();
+        if before.star.is_bar || is_on_arm {
+            assert_eq!(star_bits(before), star_bits(after), "{:?}", before);
+            bar += before.star.is_bar as usize;
+            arm += is_on_arm as usize;
+        }
+    }
+    assert!(bar > 100 && arm > 100, "{} {}", bar, arm);
+}
+
+#[test]
+fn more_damping_means_smaller_disturbances() {
+    let weak = GalaxySettings {
+        ellipse_disturbances: 2,
+        ellipse_disturbances_damping: 80,
+        ..settings()
+    };
+    let strong = GalaxySettings {
+        ellipse_disturbances_damping: 8,
+        ..weak.clone()
+    };
+    let rad = weak.far_field_radius / 16.;
+    let (weak_scale, weak_tilt) = weak.get_ellipse_disturbance(rad);
+    let (strong_scale, strong_tilt) = strong.get_ellipse_disturbance(rad);
+    assert!((strong_scale - 1.).abs() > (weak_scale - 1.).abs() * 9.);
+    assert!(strong_tilt.abs() > weak_tilt.abs() * 9.);
+}
+
+#[test]
+fn ellipticals_are_not_disturbed() {
+    let plain = GalaxySettings {
+        morphology: Morphology::Elliptical,
+        ..settings()
+    };
+    let disturbed = GalaxySettings {
+        ellipse_disturbances: 4,
+        ellipse_disturbances_damping: 5,
+        ..plain.clone()
+    };
+    for (a, b) in generate(&plain).iter().zip(&generate(&disturbed)) {
+        assert_eq!(a.star.a.to_bits(), b.star.a.to_bits());
+    }
+}
@@ -0,0 +1,112 @@
+use galaxy::density_wave::DensityWave;
+use galaxy::export::{self, CatalogRecord, ExportFormat};
+use galaxy::galaxy_setting_component::GalaxySettings;
+use galaxy::generator::{self, GalaxyObject};
+use serde_json::Value;
+
+const COLUMNS: [&str; 15] = [
+    "kind",
+    "is_bar",
+    "a",
+    "b",
+    "theta0",
+    "vel_theta",
+    "tilt_angle",
+    "temp",
+    "mag",
+    "x",
+    "y",
+    "color_r",
+    "color_g",
+    "color_b",
+    "color_a",
+];
+const YEARS: f64 = 2_500_000.;
+
+fn small_galaxy() -> (GalaxySettings, Vec<GalaxyObject>) {
+    let galaxy_settings = GalaxySettings {
+        count_stars: 300,
+        count_dusts: 100,
+        count_dusts_filaments: 100,
+        count_h2: 10,
+        count_h2_core: 10,
+        seed: 42,
+        bar_length: 3000.,
+        ..Default::default()
+    };
+    let mut density_wave = DensityWave::for_galaxy(&galaxy_settings);
+    density_wave.build();
+    let objects = generator::generate(&galaxy_settings, &density_wave);
+    (galaxy_settings, objects)
+}
+
+fn export(format: ExportFormat) -> (Vec<CatalogRecord>, String) {
+    let (settings, objects) = small_galaxy();
+    let mut bytes = vec![];
+    export::write(&mut bytes, format, &objects, &settings, YEARS).unwrap();
+    let records = objects
+        .iter()
+        .map(|object| CatalogRecord::new(object, &settings, YEARS))
+        .collect();
+    (records, String::from_utf8(bytes).unwrap())
+}
+
+/// Fields of the record in the order of the columns, numbers as written by Rust
+fn record_values(record: &CatalogRecord) -> Vec<String> {
+    let value = serde_json::to_value(record).unwrap();
+    COLUMNS
+        .iter()
+        .map(|column| match &value[column] {
+            Value::String(text) => text.clone(),
+            Value::Bool(flag) => flag.to_string(),
+            Value::Number(number) => (number.as_f64().unwrap() as f32).to_string(),
+            other => panic!("{} is {}", column, other),
+        })
+        .collect()
+}
+
+#[test]
+fn csv_has_a_header_and_a_row_per_object() {
+    let (records, csv) = export(ExportFormat::Csv);
+    assert!(records.iter().any(|record| record.is_bar));
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some(COLUMNS.join(",").as_str()));
+
+    let rows: Vec<_> = lines.collect();
+    assert_eq!(rows.len(), records.len());
+    for (row, record) in rows.iter().zip(&records) {
+        let cells: Vec<_> = row.split(',').collect();
+        assert_eq!(cells, record_values(record));
+    }
+}
+
+#[test]
+fn jsonl_records_read_back_exactly() {
+    let (records, jsonl) = export(ExportFormat::Jsonl);
+    let lines: Vec<_> = jsonl.lines().collect();
+    assert_eq!(lines.len(), records.len());
+    for (line, record) in lines.iter().zip(&records) {
+        let value: Value = serde_json::from_str(line).unwrap();
+        let keys: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        let mut columns = COLUMNS.map(String::from).to_vec();
+        columns.sort();
+        assert_eq!(keys, columns);
+
+        assert_eq!(value["kind"], record.kind);
+        assert_eq!(value["is_bar"], record.is_bar);
+        for (column, expected) in COLUMNS[2..].iter().zip(&record_values(record)[2..]) {
+            let read = value[column].as_f64().unwrap() as f32;
+            assert_eq!(read.to_string(), *expected, "{}", column);
+        }
+    }
+}
+
+#[test]
+fn formats_follow_the_extension() {
+    for format in ExportFormat::ALL {
+        let path = std::path::PathBuf::from("catalog").with_extension(format.extension());
+        assert_eq!(ExportFormat::from_path(&path), Some(format));
+    }
+    assert_eq!("NDJSON".parse(), Ok(ExportFormat::Jsonl));
+    assert!("xml".parse::<ExportFormat>().is_err());
+}
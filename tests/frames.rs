@@ -0,0 +1,30 @@
+use galaxy::frames::{self, FrameClock};
+use std::path::Path;
+
+#[test]
+fn frames_are_a_fixed_step_apart() {
+    let clock = FrameClock {
+        fps: 30.,
+        start_years: 5e6,
+        years_per_second: 3e6,
+    };
+    assert_eq!(clock.years(0), 5e6);
+    assert_eq!(clock.years(30), 8e6);
+    assert_eq!(clock.years(90), 14e6);
+    assert_eq!(clock.seconds(45), 1.5);
+    // Both ends of the duration are drawn
+    assert_eq!(clock.frame_count(2.), 61);
+    assert_eq!(clock.frame_count(0.), 1);
+}
+
+#[test]
+fn frame_names_sort_in_order() {
+    let dir = Path::new("out");
+    assert_eq!(frames::frame_path(dir, 7), dir.join("frame_000007.png"));
+    let names: Vec<_> = [9, 10, 100]
+        .map(|frame| frames::frame_path(dir, frame))
+        .to_vec();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+}
@@ -0,0 +1,218 @@
+mod common;
+
+use galaxy::density_wave::DensityWave;
+use galaxy::galaxy_setting_component::GalaxySettings;
+use galaxy::generator::{self, GalaxyObject};
+use galaxy::star::{ObjectKind, Star};
+
+fn settings(seed: u64) -> (GalaxySettings, DensityWave) {
+    let galaxy_settings = GalaxySettings {
+        count_stars: 1000,
+        count_dusts: 300,
+        count_dusts_filaments: 200,
+        count_h2: 30,
+        count_h2_core: 30,
+        seed,
+        ..Default::default()
+    };
+    let mut density_wave = DensityWave::for_galaxy(&galaxy_settings);
+    density_wave.build();
+    (galaxy_settings, density_wave)
+}
+
+fn star_bits(star: &Star) -> [u32; 7] {
+    [
+        star.theta0,
+        star.vel_theta,
+        star.tilt_angle,
+        star.a,
+        star.b,
+        star.temp,
+        star.mag,
+    ]
+    .map(f32::to_bits)
+}
+
+fn object_bits(objects: &[GalaxyObject]) -> Vec<(ObjectKind, [u32; 7])> {
+    objects
+        .iter()
+        .map(|object| (object.kind, star_bits(&object.star)))
+        .collect()
+}
+
+#[test]
+fn same_seed_same_galaxy() {
+    let (galaxy_settings, density_wave) = settings(7);
+    let first = generator::generate(&galaxy_settings, &density_wave);
+    let second = generator::generate(&galaxy_settings, &density_wave);
+    assert_eq!(object_bits(&first), object_bits(&second));
+
+    let (other_settings, density_wave) = settings(8);
+    let other = generator::generate(&other_settings, &density_wave);
+    assert_ne!(object_bits(&first), object_bits(&other));
+}
+
+fn objects_bits(stars: &[Star]) -> Vec<[u32; 7]> {
+    stars.iter().map(star_bits).collect()
+}
+
+#[test]
+fn more_objects_keep_the_ones_before() {
+    // Both star counts brighten the same first 20 stars and dust
+    let (small, density_wave) = settings(7);
+    let small = GalaxySettings {
+        count_stars: 1200,
+        ..small
+    };
+    let large = GalaxySettings {
+        count_stars: 1230,
+        count_dusts: 340,
+        count_dusts_filaments: 500,
+        count_h2: 45,
+        count_h2_core: 45,
+        ..small.clone()
+    };
+    for kind in ObjectKind::ALL {
+        let head = generator::create_objects(kind, &small, &density_wave, small.seed);
+        let all = generator::create_objects(kind, &large, &density_wave, large.seed);
+        assert!(head.len() < all.len(), "{:?}", kind);
+        assert_eq!(objects_bits(&head), objects_bits(&all[..head.len()]));
+
+        let tail = generator::create_object_range(
+            kind,
+            &large,
+            &density_wave,
+            large.seed,
+            head.len()..usize::MAX,
+        );
+        assert_eq!(objects_bits(&all[head.len()..]), objects_bits(&tail));
+    }
+}
+
+#[test]
+fn count_changes_equal_a_fresh_generation() {
+    let (small, density_wave) = settings(7);
+    let large = GalaxySettings {
+        count_stars: 2500,
+        count_dusts: 200,
+        count_dusts_filaments: 400,
+        count_h2: 10,
+        ..small.clone()
+    };
+    for (from, to) in [(&small, &large), (&large, &small)] {
+        for kind in ObjectKind::ALL {
+            // What the application does: drop the end, draw again the objects
+            // with a new brightness and add the new end
+            let mut objects = generator::create_objects(kind, from, &density_wave, from.seed);
+            let existing = objects.len();
+            let count = generator::object_count(kind, to, to.seed);
+            let (before, after) = (
+                generator::bright_count(kind, from),
+                generator::bright_count(kind, to),
+            );
+            let redrawn = before.min(after)..before.max(after).min(existing).min(count);
+            objects.truncate(count);
+            objects.splice(
+                redrawn.clone(),
+                generator::create_object_range(kind, to, &density_wave, to.seed, redrawn),
+            );
+            objects.extend(generator::create_object_range(
+                kind,
+                to,
+                &density_wave,
+                to.seed,
+                existing..count,
+            ));
+
+            let fresh = generator::create_objects(kind, to, &density_wave, to.seed);
+            assert_eq!(fresh.len(), count, "{:?}", kind);
+            assert_eq!(objects_bits(&objects), objects_bits(&fresh), "{:?}", kind);
+        }
+    }
+}
+
+#[test]
+fn h2_cores_follow_their_own_count() {
+    let (galaxy_settings, density_wave) = settings(7);
+    let galaxy_settings = GalaxySettings {
+        count_h2: 30,
+        count_h2_core: 12,
+        ..galaxy_settings
+    };
+    let cores = generator::create_objects(
+        ObjectKind::H2Core,
+        &galaxy_settings,
+        &density_wave,
+        galaxy_settings.seed,
+    );
+    assert_eq!(cores.len(), 12);
+    assert_eq!(
+        generator::object_count(ObjectKind::H2Core, &galaxy_settings, galaxy_settings.seed),
+        12
+    );
+}
+
+#[test]
+fn inactive_options_are_inert() {
+    let plain = common::settings();
+    let cases = [
+        (
+            "bar settings without a bar",
+            GalaxySettings {
+                bar_axis_ratio: 0.5,
+                bar_pattern_speed: 80.,
+                bar_star_fraction: 0.9,
+                ..plain.clone()
+            },
+        ),
+        (
+            "elliptical settings of a spiral",
+            GalaxySettings {
+                elliptical_class: 5,
+                sersic_index: 2.,
+                ..plain.clone()
+            },
+        ),
+        (
+            "arm settings of the density wave model",
+            GalaxySettings {
+                arm_count: 5,
+                arm_pitch: 30.,
+                arm_width: 100.,
+                ..plain.clone()
+            },
+        ),
+        (
+            "damping without disturbances",
+            GalaxySettings {
+                ellipse_disturbances_damping: 3,
+                ..plain.clone()
+            },
+        ),
+    ];
+
+    let expected = common::generate(&plain);
+    let years = 1e8;
+    let positions = |settings: &GalaxySettings, objects: &[GalaxyObject]| -> Vec<[u32; 2]> {
+        objects
+            .iter()
+            .map(|object| {
+                object
+                    .star
+                    .position(settings, years)
+                    .to_array()
+                    .map(f32::to_bits)
+            })
+            .collect()
+    };
+    for (name, settings) in cases {
+        let objects = common::generate(&settings);
+        assert_eq!(object_bits(&objects), object_bits(&expected), "{}", name);
+        assert_eq!(
+            positions(&settings, &objects),
+            positions(&plain, &expected),
+            "{}",
+            name
+        );
+    }
+}
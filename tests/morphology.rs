@@ -0,0 +1,115 @@
+mod common;
+
+use common::{fields, generate};
+use galaxy::galaxy_setting_component::{GalaxySettings, Morphology};
+use galaxy::generator::GalaxyObject;
+use galaxy::star::ObjectKind;
+use galaxy::validation;
+
+fn settings(morphology: Morphology) -> GalaxySettings {
+    GalaxySettings {
+        morphology,
+        count_dusts_filaments: 1000,
+        ..common::settings()
+    }
+}
+
+fn mean_temp(objects: &[GalaxyObject]) -> f32 {
+    let stars: Vec<_> = objects
+        .iter()
+        .filter(|object| object.kind == ObjectKind::Star)
+        .collect();
+    stars.iter().map(|object| object.star.temp).sum::<f32>() / stars.len() as f32
+}
+
+#[test]
+fn ellipticals_are_only_old_stars() {
+    let spiral = generate(&settings(Morphology::Spiral));
+    let elliptical = generate(&settings(Morphology::Elliptical));
+    assert!(elliptical
+        .iter()
+        .all(|object| object.kind == ObjectKind::Star));
+    assert_eq!(elliptical.len(), 4000);
+    assert!(mean_temp(&elliptical) < mean_temp(&spiral) - 1000.);
+}
+
+#[test]
+fn elliptical_class_sets_the_axis_ratio() {
+    for class in [0, 3, 7] {
+        let settings = GalaxySettings {
+            elliptical_class: class,
+            ..settings(Morphology::Elliptical)
+        };
+        let ratio = 1. - class as f32 / 10.;
+        for object in generate(&settings).iter().skip(1) {
+            let star = &object.star;
+            assert!((star.b - star.a * ratio).abs() <= star.a * 1e-5);
+            assert!(star.a <= settings.far_field_radius);
+        }
+    }
+}
+
+#[test]
+fn half_of_an_elliptical_is_inside_the_effective_radius() {
+    for sersic_index in [1., 4.] {
+        let settings = GalaxySettings {
+            sersic_index,
+            count_stars: 20000,
+            // Far enough out not to cut off the light of the profile
+            far_field_radius: 200_000.,
+            ..settings(Morphology::Elliptical)
+        };
+        let mut radii: Vec<_> = generate(&settings)
+            .iter()
+            .map(|object| object.star.a)
+            .collect();
+        radii.sort_by(f32::total_cmp);
+        let median = radii[radii.len() / 2] / settings.get_effective_radius();
+        assert!((median - 1.).abs() < 0.1, "{} {}", sersic_index, median);
+    }
+}
+
+#[test]
+fn lenticulars_are_discs_without_arms() {
+    let settings = settings(Morphology::Lenticular);
+    let objects = generate(&settings);
+    assert!(objects
+        .iter()
+        .all(|object| matches!(object.kind, ObjectKind::Star | ObjectKind::Dust)));
+
+    // Orbits at the same radius point every way instead of winding into arms
+    let tilts: Vec<_> = objects
+        .iter()
+        .filter(|object| object.kind == ObjectKind::Star && object.star.a > 1000.)
+        .map(|object| object.star.tilt_angle)
+        .collect();
+    let below_half_turn = tilts
+        .iter()
+        .filter(|tilt| **tilt < std::f32::consts::PI)
+        .count() as f32;
+    assert!((below_half_turn / tilts.len() as f32 - 0.5).abs() < 0.05);
+
+    let position = objects[10].star.position(&settings, 0.);
+    let ellipse_radius = objects[10].star.a.max(objects[10].star.b);
+    assert!(position.length() <= ellipse_radius * 1.001);
+}
+
+#[test]
+fn morphology_is_read_by_name() {
+    let mut value = serde_json::to_value(GalaxySettings::default()).unwrap();
+    assert_eq!(value["morphology"], "spiral");
+    value["morphology"] = serde_json::json!("lenticular");
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(settings.morphology, Morphology::Lenticular);
+
+    value["morphology"] = serde_json::json!("irregular");
+    value["elliptical_class"] = serde_json::json!(9);
+    value["sersic_index"] = serde_json::json!(0.1);
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    assert_eq!(
+        fields(&problems),
+        ["morphology", "elliptical_class", "sersic_index"]
+    );
+    assert_eq!(settings.morphology, Morphology::Spiral);
+}
@@ -0,0 +1,33 @@
+use galaxy::preset;
+
+#[test]
+fn typed_names_are_fit_for_a_file() {
+    assert_eq!(preset::clean_name("  my galaxy "), Some("my_galaxy".into()));
+    assert_eq!(preset::clean_name("../M-31"), Some("M-31".into()));
+    assert_eq!(preset::clean_name(" /.\\ "), None);
+}
+
+#[test]
+fn renaming_keeps_other_presets() {
+    let dir = std::env::temp_dir().join(format!("galaxy_preset_rename_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["first", "second"] {
+        std::fs::write(preset::preset_path(&dir, name), "{}").unwrap();
+    }
+
+    assert!(preset::rename_preset(&dir, "first", "second").is_err());
+    preset::rename_preset(&dir, "first", "third").unwrap();
+    assert_eq!(preset::list_presets(&dir), ["second", "third"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn names_with_dots_are_kept() {
+    let dir = std::env::temp_dir().join(format!("galaxy_preset_dots_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = preset::preset_path(&dir, "m.31");
+    assert_eq!(path.file_name().unwrap(), "m.31.json");
+    std::fs::write(&path, "{}").unwrap();
+    assert_eq!(preset::list_presets(&dir), ["m.31"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,168 @@
+use galaxy::density_wave::{sersic_b, DensityWave, Profile};
+use galaxy::galaxy_setting_component::{GalaxySettings, Morphology};
+use galaxy::generator;
+use galaxy::validation;
+
+fn wave(profile: Profile) -> DensityWave {
+    let mut wave = DensityWave {
+        profile,
+        ..Default::default()
+    };
+    wave.build();
+    wave
+}
+
+fn assert_close(a: f32, b: f32, tolerance: f32) {
+    assert!((a - b).abs() <= b.abs() * tolerance, "{} != {}", a, b);
+}
+
+#[test]
+fn half_of_the_light_is_inside_the_effective_radius() {
+    for n in [1., 2.] {
+        let settings = GalaxySettings {
+            morphology: Morphology::Elliptical,
+            sersic_index: n,
+            count_stars: 20000,
+            far_field_radius: 200_000.,
+            ..Default::default()
+        };
+        let effective_radius = settings.get_effective_radius();
+        let mut wave = DensityWave {
+            profile: Profile::Sersic {
+                n,
+                effective_radius,
+            },
+            // Far enough out not to cut off the light of the profile
+            max: 40. * effective_radius,
+            steps: 20000,
+            ..Default::default()
+        };
+        wave.build();
+        let half_light = wave.val_from_prob(0.5);
+        assert_close(half_light, effective_radius, 0.02);
+
+        // Ellipticals sample the same Sersic light with their own sampler, not the wave
+        let mut radii: Vec<_> = generator::generate(&settings, &wave)
+            .iter()
+            .map(|object| object.star.a)
+            .collect();
+        radii.sort_by(f32::total_cmp);
+        assert_close(radii[radii.len() / 2], half_light, 0.05);
+    }
+}
+
+#[test]
+fn sersic_of_index_one_is_exponential() {
+    let effective_radius = 3000.;
+    let sersic = wave(Profile::Sersic {
+        n: 1.,
+        effective_radius,
+    });
+    let exponential = wave(Profile::Exponential {
+        scale_length: effective_radius / sersic_b(1.),
+    });
+    for p in [0.1, 0.5, 0.8] {
+        assert_close(sersic.val_from_prob(p), exponential.val_from_prob(p), 0.01);
+    }
+    // Higher indices have steeper centres
+    let steep = wave(Profile::Sersic {
+        n: 4.,
+        effective_radius,
+    });
+    assert!(steep.val_from_prob(0.1) < sersic.val_from_prob(0.1));
+}
+
+#[test]
+fn king_profile_ends_at_the_tidal_radius() {
+    let wave = wave(Profile::King {
+        core_radius: 500.,
+        tidal_radius: 8000.,
+    });
+    assert!(wave.val_from_prob(0.999) < 8000.);
+    assert!(wave.val_from_prob(0.5) < 4000.);
+}
+
+#[test]
+fn double_exponential_changes_slope_at_the_break() {
+    let inner = wave(Profile::Exponential {
+        scale_length: 4000.,
+    });
+    let truncated = wave(Profile::DoubleExponential {
+        inner_scale_length: 4000.,
+        outer_scale_length: 1000.,
+        break_radius: 8000.,
+    });
+    // Same shape inside the break, the outer part has fewer stars
+    let inside = truncated.val_from_prob(0.3);
+    assert!(inside < 8000.);
+    assert!(truncated.val_from_prob(0.99) < inner.val_from_prob(0.99));
+}
+
+#[test]
+fn profile_is_read_from_json() {
+    let galaxy = GalaxySettings::default();
+    let mut value = serde_json::to_value(DensityWave::default()).unwrap();
+    assert_eq!(value["profile"]["type"], "bulge_disc");
+
+    value["profile"] = serde_json::json!({"type": "sersic", "n": 2.5, "effective_radius": 3000.});
+    let (wave, problems) = validation::density_wave_from_value(&value, &galaxy);
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(
+        wave.profile,
+        Profile::Sersic {
+            n: 2.5,
+            effective_radius: 3000.
+        }
+    );
+
+    value.as_object_mut().unwrap().remove("profile");
+    let (wave, problems) = validation::density_wave_from_value(&value, &galaxy);
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(wave.profile, Profile::BulgeDisc);
+}
+
+#[test]
+fn ellipticals_report_the_profile_they_ignore() {
+    let galaxy = GalaxySettings {
+        morphology: Morphology::Elliptical,
+        ..Default::default()
+    };
+    let mut value = serde_json::to_value(DensityWave::default()).unwrap();
+    let (_, problems) = validation::density_wave_from_value(&value, &galaxy);
+    assert!(problems.is_empty(), "{:?}", problems);
+
+    value["profile"] = serde_json::json!({"type": "exponential", "scale_length": 3000.});
+    let (wave, problems) = validation::density_wave_from_value(&value, &galaxy);
+    assert_eq!(
+        problems.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        ["profile: ignored by ellipticals, they follow sersic_index"]
+    );
+    assert_eq!(
+        wave.profile,
+        Profile::Exponential {
+            scale_length: 3000.
+        }
+    );
+}
+
+#[test]
+fn invalid_profiles_fall_back_to_bulge_and_disc() {
+    let galaxy = GalaxySettings::default();
+    let mut value = serde_json::to_value(DensityWave::default()).unwrap();
+    for profile in [
+        serde_json::json!({"type": "king", "core_radius": 2000., "tidal_radius": 1000.}),
+        serde_json::json!({"type": "exponential", "scale_length": -1.}),
+        serde_json::json!({"type": "sersic", "n": 4.}),
+        serde_json::json!({"type": "hubble"}),
+    ] {
+        value["profile"] = profile;
+        let (wave, problems) = validation::density_wave_from_value(&value, &galaxy);
+        let fields: Vec<_> = problems
+            .iter()
+            .map(|problem| problem.field.as_str())
+            .collect();
+        assert_eq!(fields, ["profile"]);
+        assert_eq!(wave.profile, Profile::BulgeDisc);
+        assert!(wave.y2.iter().all(|value| value.is_finite()));
+    }
+}
@@ -0,0 +1,17 @@
+use galaxy::galaxy_setting_component::GalaxySettings;
+use galaxy::render::{self, Bloom, Particle, View};
+
+#[test]
+fn tiny_images_render_with_bloom() {
+    let settings = GalaxySettings::default();
+    for (width, height) in [(1, 1), (2, 3), (16, 1)] {
+        let view = View {
+            width,
+            height,
+            ..Default::default()
+        };
+        let bloom = Bloom::for_scale(view.scale);
+        let image = render::render(&[], &settings, &view, Some(&bloom), &Particle::default());
+        assert_eq!(image.dimensions(), (width, height));
+    }
+}
@@ -0,0 +1,186 @@
+use galaxy::density_wave::{DensityWave, Profile};
+use galaxy::galaxy_setting_component::{GalaxySettings, Morphology};
+use galaxy::generator::{self, GalaxyObject};
+use galaxy::snapshot::{self, Snapshot, SnapshotError};
+use galaxy::star::Star;
+
+fn small_galaxy() -> Snapshot {
+    let galaxy_settings = GalaxySettings {
+        count_stars: 500,
+        count_dusts: 200,
+        count_dusts_filaments: 100,
+        count_h2: 20,
+        count_h2_core: 20,
+        seed: 42,
+        ..Default::default()
+    };
+    let mut density_wave = DensityWave::for_galaxy(&galaxy_settings);
+    density_wave.build();
+    let objects = generator::generate(&galaxy_settings, &density_wave);
+    Snapshot {
+        galaxy_settings,
+        density_wave,
+        years: 123_456_789.5,
+        objects,
+    }
+}
+
+fn star_bits(star: &Star) -> [u32; 7] {
+    [
+        star.theta0,
+        star.vel_theta,
+        star.tilt_angle,
+        star.a,
+        star.b,
+        star.temp,
+        star.mag,
+    ]
+    .map(f32::to_bits)
+}
+
+fn assert_same_objects(expected: &[GalaxyObject], actual: &[GalaxyObject]) {
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(actual) {
+        assert_eq!(expected.kind, actual.kind);
+        assert_eq!(star_bits(&expected.star), star_bits(&actual.star));
+        assert_eq!(expected.star.is_bar, actual.star.is_bar);
+    }
+}
+
+fn round_trip(snapshot: &Snapshot) -> Snapshot {
+    let mut bytes = vec![];
+    snapshot.write(&mut bytes).unwrap();
+    Snapshot::read(&mut bytes.as_slice()).unwrap()
+}
+
+#[test]
+fn generated_objects_round_trip_exactly() {
+    let snapshot = small_galaxy();
+    let loaded = round_trip(&snapshot);
+
+    assert_same_objects(&snapshot.objects, &loaded.objects);
+    assert_eq!(loaded.years.to_bits(), snapshot.years.to_bits());
+    assert!(loaded.galaxy_settings == snapshot.galaxy_settings);
+    assert!(loaded.density_wave == snapshot.density_wave);
+}
+
+#[test]
+fn edited_objects_round_trip_exactly() {
+    let mut snapshot = small_galaxy();
+    snapshot.objects.truncate(10);
+    snapshot.objects[3].star.temp = 12_345.678;
+    snapshot.objects[5].star.a = f32::MIN_POSITIVE;
+    snapshot.years = -1e12;
+
+    let loaded = round_trip(&snapshot);
+    assert_same_objects(&snapshot.objects, &loaded.objects);
+    assert_eq!(loaded.years, -1e12);
+}
+
+#[test]
+fn bar_stars_stay_on_the_bar() {
+    let mut snapshot = small_galaxy();
+    snapshot.galaxy_settings.bar_length = 3000.;
+    snapshot.objects = generator::generate(&snapshot.galaxy_settings, &snapshot.density_wave);
+    assert!(snapshot.objects.iter().any(|object| object.star.is_bar));
+
+    let loaded = round_trip(&snapshot);
+    assert_same_objects(&snapshot.objects, &loaded.objects);
+}
+
+#[test]
+fn version_1_snapshots_are_read() {
+    let snapshot = small_galaxy();
+    let mut bytes = vec![];
+    snapshot.write(&mut bytes).unwrap();
+
+    // Version 1 is the same without the flags byte after the kind
+    let settings_len = u32::from_le_bytes(bytes[6..10].try_into().unwrap()) as usize;
+    let objects_start = 10 + settings_len + 24;
+    let mut version_1 = bytes[..objects_start].to_vec();
+    version_1[4..6].copy_from_slice(&1u16.to_le_bytes());
+    for object in bytes[objects_start..].chunks(2 + 7 * 4) {
+        version_1.push(object[0]);
+        version_1.extend_from_slice(&object[2..]);
+    }
+
+    let loaded = Snapshot::read(&mut version_1.as_slice()).unwrap();
+    assert_same_objects(&snapshot.objects, &loaded.objects);
+}
+
+#[test]
+fn loaded_snapshot_matches_regenerated_galaxy() {
+    let snapshot = small_galaxy();
+    let loaded = round_trip(&snapshot);
+
+    let regenerated = generator::generate(&loaded.galaxy_settings, &loaded.density_wave);
+    assert_same_objects(&regenerated, &loaded.objects);
+}
+
+#[test]
+fn save_and_load_file() {
+    let snapshot = small_galaxy();
+    let name = format!("galaxy_test.{}", std::process::id());
+    let path = snapshot::snapshot_path(&std::env::temp_dir(), &name);
+    assert!(path
+        .to_string_lossy()
+        .ends_with(&format!("{}.snapshot", name)));
+    snapshot.save(&path).unwrap();
+    let result = Snapshot::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let loaded = result.unwrap();
+    assert_same_objects(&snapshot.objects, &loaded.objects);
+}
+
+#[test]
+fn rejects_other_files() {
+    let result = Snapshot::read(&mut b"{\"galaxy_settings\": {}}".as_slice());
+    assert!(matches!(result, Err(SnapshotError::NotASnapshot)));
+}
+
+#[test]
+fn rejects_newer_versions() {
+    let mut bytes = vec![];
+    small_galaxy().write(&mut bytes).unwrap();
+    bytes[4..6].copy_from_slice(&99u16.to_le_bytes());
+
+    let result = Snapshot::read(&mut bytes.as_slice());
+    assert!(matches!(result, Err(SnapshotError::UnsupportedVersion(99))));
+}
+
+#[test]
+fn rejects_settings_a_preset_would_reset() {
+    let mut snapshot = small_galaxy();
+    snapshot.galaxy_settings.bulge_radius = snapshot.galaxy_settings.radius * 2.;
+    let mut bytes = vec![];
+    snapshot.write(&mut bytes).unwrap();
+
+    let result = Snapshot::read(&mut bytes.as_slice());
+    let Err(SnapshotError::InvalidSettings(problems)) = result else {
+        panic!("invalid settings are read");
+    };
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].field, "galaxy_settings.bulge_radius");
+}
+
+#[test]
+fn ignored_settings_are_kept() {
+    let mut snapshot = small_galaxy();
+    snapshot.galaxy_settings.morphology = Morphology::Elliptical;
+    snapshot.density_wave.profile = Profile::Exponential {
+        scale_length: 3000.,
+    };
+    let loaded = round_trip(&snapshot);
+    assert!(loaded.density_wave.profile == snapshot.density_wave.profile);
+}
+
+#[test]
+fn rejects_truncated_files() {
+    let mut bytes = vec![];
+    small_galaxy().write(&mut bytes).unwrap();
+    bytes.truncate(bytes.len() - 3);
+
+    let result = Snapshot::read(&mut bytes.as_slice());
+    assert!(matches!(result, Err(SnapshotError::Io(_))));
+}
@@ -0,0 +1,104 @@
+use bevy_math::Vec2;
+use galaxy::spatial_index::SpatialGrid;
+use rand::prelude::*;
+
+fn random_points(count: usize) -> Vec<(Vec2, usize)> {
+    let mut rnd = StdRng::seed_from_u64(7);
+    (0..count)
+        .map(|i| {
+            let position = Vec2::new(rnd.gen_range(-5000.0..5000.), rnd.gen_range(-3000.0..3000.));
+            (position, i)
+        })
+        .collect()
+}
+
+fn sorted(items: impl Iterator<Item = (Vec2, usize)>) -> Vec<usize> {
+    let mut items: Vec<usize> = items.map(|(_, item)| item).collect();
+    items.sort_unstable();
+    items
+}
+
+#[test]
+fn radius_query_matches_brute_force() {
+    let points = random_points(2000);
+    let grid = SpatialGrid::from_points(250., points.iter().copied());
+    assert_eq!(grid.len(), points.len());
+
+    for (center, radius) in [
+        (Vec2::ZERO, 300.),
+        (Vec2::new(4900., -2900.), 700.),
+        (Vec2::new(-123.4, 567.8), 0.),
+        (Vec2::new(20000., 0.), 1000.),
+        (Vec2::ZERO, 1e9),
+    ] {
+        let expected = sorted(
+            points
+                .iter()
+                .copied()
+                .filter(|(position, _)| position.distance(center) <= radius),
+        );
+        assert_eq!(sorted(grid.query_radius(center, radius)), expected);
+    }
+}
+
+#[test]
+fn rect_query_matches_brute_force() {
+    let points = random_points(2000);
+    let grid = SpatialGrid::from_points(100., points.iter().copied());
+
+    for (min, max) in [
+        (Vec2::new(-1000., -500.), Vec2::new(250., 1250.)),
+        (Vec2::new(-1e7, -1e7), Vec2::new(1e7, 1e7)),
+        (Vec2::new(10., 10.), Vec2::new(-10., -10.)),
+    ] {
+        let expected = sorted(points.iter().copied().filter(|(position, _)| {
+            position.x >= min.x && position.y >= min.y && position.x <= max.x && position.y <= max.y
+        }));
+        assert_eq!(sorted(grid.query_rect(min, max)), expected);
+    }
+}
+
+#[test]
+fn nearest_matches_brute_force() {
+    let points = random_points(2000);
+    let grid = SpatialGrid::from_points(250., points.iter().copied());
+
+    let mut rnd = StdRng::seed_from_u64(11);
+    for _ in 0..200 {
+        let position = Vec2::new(rnd.gen_range(-8000.0..8000.), rnd.gen_range(-8000.0..8000.));
+        let expected = points
+            .iter()
+            .map(|(point, _)| point.distance(position))
+            .fold(f32::INFINITY, f32::min);
+        let (nearest, _) = grid.nearest(position, f32::INFINITY).unwrap();
+        assert_eq!(nearest.distance(position), expected);
+    }
+}
+
+#[test]
+fn nearest_respects_max_distance() {
+    let grid = SpatialGrid::from_points(10., [(Vec2::new(100., 0.), 1), (Vec2::new(0., 50.), 2)]);
+
+    assert_eq!(grid.nearest(Vec2::ZERO, 49.), None);
+    assert_eq!(grid.nearest(Vec2::ZERO, 50.).map(|(_, item)| item), Some(2));
+    assert_eq!(
+        grid.nearest(Vec2::new(1e30, 0.), f32::INFINITY)
+            .map(|(_, item)| item),
+        Some(1)
+    );
+}
+
+#[test]
+fn cleared_grid_is_empty() {
+    let mut grid = SpatialGrid::from_points(10., random_points(100));
+    grid.clear();
+
+    assert!(grid.is_empty());
+    assert_eq!(grid.nearest(Vec2::ZERO, f32::INFINITY), None);
+    assert_eq!(grid.query_radius(Vec2::ZERO, 1e9).count(), 0);
+
+    grid.insert(Vec2::new(f32::NAN, 0.), 1);
+    grid.insert(Vec2::new(3., 4.), 2);
+    assert_eq!(grid.len(), 1);
+    assert_eq!(grid.nearest(Vec2::ZERO, 5.), Some((Vec2::new(3., 4.), 2)));
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use common::fields;
+use galaxy::density_wave::{DensityWave, Profile};
+use galaxy::galaxy_setting_component::GalaxySettings;
+use galaxy::validation;
+use serde_json::json;
+
+fn default_wave_value() -> serde_json::Value {
+    serde_json::to_value(DensityWave::for_galaxy(&GalaxySettings::default())).unwrap()
+}
+
+#[test]
+fn defaults_have_no_problems() {
+    let value = serde_json::to_value(GalaxySettings::default()).unwrap();
+    let (_, problems) = validation::galaxy_settings_from_value(&value);
+    assert!(problems.is_empty(), "{:?}", problems);
+
+    let (_, problems) =
+        validation::density_wave_from_value(&default_wave_value(), &GalaxySettings::default());
+    assert!(problems.is_empty(), "{:?}", problems);
+}
+
+#[test]
+fn wrong_types_keep_the_default() {
+    let mut value = serde_json::to_value(GalaxySettings::default()).unwrap();
+    value["radius"] = json!("large");
+    value["count_stars"] = json!(-5);
+    value["has_dark_matter"] = json!(1);
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    let default = GalaxySettings::default();
+    assert_eq!(
+        fields(&problems),
+        ["radius", "count_stars", "has_dark_matter"]
+    );
+    assert_eq!(settings.radius, default.radius);
+    assert_eq!(settings.count_stars, default.count_stars);
+    assert_eq!(settings.has_dark_matter, default.has_dark_matter);
+}
+
+#[test]
+fn out_of_range_values_fall_back_to_defaults() {
+    let default = GalaxySettings::default();
+    let mut value = serde_json::to_value(&default).unwrap();
+    value["radius"] = json!(-1.);
+    value["bulge_radius"] = json!(1e9);
+    value["ellipse_disturbances_damping"] = json!(1);
+    value["bar_star_fraction"] = json!(1.5);
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    assert_eq!(
+        fields(&problems),
+        [
+            "radius",
+            "bulge_radius",
+            "ellipse_disturbances_damping",
+            "bar_star_fraction"
+        ]
+    );
+    assert_eq!(settings.radius, default.radius);
+    assert_eq!(settings.bulge_radius, default.bulge_radius);
+    assert_eq!(
+        settings.ellipse_disturbances_damping,
+        default.ellipse_disturbances_damping
+    );
+    assert_eq!(settings.bar_star_fraction, default.bar_star_fraction);
+
+    let mut wave = default_wave_value();
+    wave["steps"] = json!(101);
+    wave["max"] = json!(0.);
+    let (read, problems) = validation::density_wave_from_value(&wave, &default);
+    assert_eq!(fields(&problems), ["steps", "max"]);
+    let default_wave = DensityWave::for_galaxy(&default);
+    assert_eq!(read.steps, default_wave.steps);
+    assert_eq!(read.max, default_wave.max);
+}
+
+#[test]
+fn missing_fields_keep_the_default() {
+    let mut value = serde_json::to_value(GalaxySettings::default()).unwrap();
+    value.as_object_mut().unwrap().remove("radius");
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    assert_eq!(fields(&problems), ["radius"]);
+    assert!(problems[0].message.starts_with("missing"));
+    assert_eq!(settings.radius, GalaxySettings::default().radius);
+}
+
+#[test]
+fn missing_optional_fields_are_not_problems() {
+    let mut value = serde_json::to_value(GalaxySettings::default()).unwrap();
+    let map = value.as_object_mut().unwrap();
+    for field in [
+        "arm_model",
+        "arm_count",
+        "arm_pitch",
+        "arm_width",
+        "bar_length",
+        "bar_axis_ratio",
+        "bar_pattern_speed",
+        "bar_star_fraction",
+        "morphology",
+        "elliptical_class",
+        "sersic_index",
+    ] {
+        map.remove(field);
+    }
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(settings.arm_count, GalaxySettings::default().arm_count);
+    assert!(!settings.has_bar());
+    assert!(settings.has_arms());
+    assert!(!settings.has_log_spiral_arms());
+
+    let mut wave = default_wave_value();
+    wave.as_object_mut().unwrap().remove("profile");
+    let (read, problems) = validation::density_wave_from_value(&wave, &GalaxySettings::default());
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(read.profile, Profile::BulgeDisc);
+}
+
+#[test]
+fn problems_say_what_is_used_instead() {
+    let mut value = serde_json::to_value(GalaxySettings::default()).unwrap();
+    value["pert_n"] = json!(-2);
+    value["unknown"] = json!(1);
+    value.as_object_mut().unwrap().remove("seed");
+    let (settings, problems) = validation::galaxy_settings_from_value(&value);
+    let messages: Vec<_> = problems.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            format!("seed: missing, using {}", settings.seed),
+            "unknown: unknown field, ignored".to_string(),
+            format!(
+                "pert_n: must be at least 0, got -2, using {}",
+                settings.pert_n
+            ),
+        ]
+    );
+
+    let (_, problems) = validation::galaxy_settings_from_str("{ not json");
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].field, "");
+    assert!(problems[0].message.starts_with("invalid json"));
+    assert!(problems[0].message.ends_with("using defaults"));
+
+    let (_, problems) = validation::galaxy_settings_from_value(&json!([1, 2]));
+    assert_eq!(
+        problems[0].to_string(),
+        "expected a json object, using defaults"
+    );
+    assert_eq!(
+        problems[0]
+            .clone()
+            .in_section("galaxy_settings")
+            .to_string(),
+        "galaxy_settings: expected a json object, using defaults"
+    );
+}